@@ -12,5 +12,5 @@ fn main() {
     let mrz_type = parser.get_mrz_type().unwrap();
     assert_eq!(mrz_type, MRZ_TYPE1);
     let result = parser.parse().unwrap();
-    assert_eq!(result.is_valid, true);
+    assert!(result.is_valid);
 }
@@ -10,5 +10,5 @@ fn main() {
     let mrz_type = parser.get_mrz_type().unwrap();
     assert_eq!(mrz_type, 3);
     let result = parser.parse().unwrap();
-    assert_eq!(result.is_valid, true);
+    assert!(result.is_valid);
 }
@@ -0,0 +1,409 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rs_mrz_parser::{
+    calculate_check_digits, CorrectionMode, MRZParser, MRZResult, MrzBuilder, MrzDate, MrzDocument, MrzError,
+    MrzFormat, ParserOptions, Sex,
+};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// Exit codes: every record parsed with valid check digits, at least one invalid check digit, and at least
+// one record that could not be read or parsed
+const EXIT_INVALID: u8 = 1;
+const EXIT_ERROR: u8 = 2;
+
+// Parse, validate and generate machine readable zones
+#[derive(Parser, Debug)]
+#[command(name = "mrz", version, about, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    parse: ParseArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Parse MRZs and check their check digits (the default command)
+    Parse(ParseArgs),
+    /// Generate the MRZ lines of a document
+    Generate(GenerateArgs),
+    /// Print the check digit of each value
+    CheckDigit {
+        /// Values to compute the check digit of
+        #[arg(required = true)]
+        values: Vec<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct ParseArgs {
+    /// MRZs to parse, one per argument, with lines separated by line breaks or concatenated
+    mrz: Vec<String>,
+
+    /// Files to read MRZs from, one record per blank-line-separated block. Standard input is read when no
+    /// MRZ or file is given.
+    #[arg(short, long = "file", value_name = "PATH")]
+    files: Vec<PathBuf>,
+
+    /// Print one JSON object per record instead of the field list
+    #[arg(long)]
+    json: bool,
+
+    /// Reject input outside of the ICAO 9303 character set or with misplaced fillers
+    #[arg(long)]
+    strict: bool,
+
+    /// Insert or remove fillers in lines up to two characters off the length of their format
+    #[arg(long)]
+    repair_line_length: bool,
+
+    /// OCR error correction applied while parsing
+    #[arg(long, value_enum, default_value_t = Correction::Aggressive)]
+    correction: Correction,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Correction {
+    Off,
+    Conservative,
+    Aggressive,
+    CheckDigitGuided,
+}
+
+impl From<Correction> for CorrectionMode {
+    fn from(correction: Correction) -> Self {
+        match correction {
+            Correction::Off => CorrectionMode::Off,
+            Correction::Conservative => CorrectionMode::Conservative,
+            Correction::Aggressive => CorrectionMode::Aggressive,
+            Correction::CheckDigitGuided => CorrectionMode::CheckDigitGuided,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    /// Document format
+    #[arg(long, value_enum)]
+    format: Format,
+
+    /// Document type, defaulting to `I` for identity cards, `P` for passports and `V` for visas
+    #[arg(long)]
+    document_type: Option<String>,
+
+    /// Three-letter code of the issuing state or organisation
+    #[arg(long)]
+    issuing_state: String,
+
+    /// Primary identifier
+    #[arg(long)]
+    surname: String,
+
+    /// Secondary identifiers, separated by spaces
+    #[arg(long, default_value = "")]
+    given_names: String,
+
+    #[arg(long)]
+    document_number: String,
+
+    /// Three-letter nationality code
+    #[arg(long, default_value = "")]
+    nationality: String,
+
+    /// Birth date as YYYY-MM-DD
+    #[arg(long, value_parser = parse_date)]
+    birth_date: Option<MrzDate>,
+
+    /// One of M, F or X, written as a filler when not given
+    #[arg(long, value_parser = parse_sex)]
+    sex: Option<Sex>,
+
+    /// Expiry date as YYYY-MM-DD
+    #[arg(long, value_parser = parse_date)]
+    expiry_date: Option<MrzDate>,
+
+    /// Optional data of the first line for TD1, or personal number / optional data of the second line
+    #[arg(long, default_value = "")]
+    optional_data: String,
+
+    /// Optional data of the second line of a TD1 document
+    #[arg(long, default_value = "")]
+    optional_data_2: String,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Td1,
+    Td2,
+    Td3,
+    Mrva,
+    Mrvb,
+}
+
+impl From<Format> for MrzFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Td1 => MrzFormat::TD1,
+            Format::Td2 => MrzFormat::TD2,
+            Format::Td3 => MrzFormat::TD3,
+            Format::Mrva => MrzFormat::MRVA,
+            Format::Mrvb => MrzFormat::MRVB,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Some(Command::Parse(args)) => parse(&args),
+        None => parse(&cli.parse),
+        Some(Command::Generate(args)) => generate(&args),
+        Some(Command::CheckDigit { values }) => check_digit(&values),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("mrz: {}", err);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+// parse: Parses every record and prints it, returning the exit code for the worst record.
+fn parse(args: &ParseArgs) -> Result<ExitCode, String> {
+    let options = ParserOptions {
+        correction: args.correction.into(),
+        strict: args.strict,
+        repair_line_length: args.repair_line_length,
+        ..ParserOptions::default()
+    };
+
+    let mut records = args.mrz.clone();
+    for path in &args.files {
+        let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        records.extend(split_records(&text));
+    }
+    if args.mrz.is_empty() && args.files.is_empty() {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|err| format!("stdin: {}", err))?;
+        records.extend(split_records(&text));
+    }
+
+    let mut code = 0;
+    for (idx, record) in records.iter().enumerate() {
+        let result = MRZParser::new_mrz_string_parser(record)
+            .with_options(options.clone())
+            .parse();
+
+        code = code.max(match &result {
+            Ok(result) if result.is_valid => 0,
+            Ok(_) => EXIT_INVALID,
+            Err(_) => EXIT_ERROR,
+        });
+
+        if args.json {
+            println!("{}", json_record(idx + 1, &result));
+        } else {
+            print_record(idx + 1, &result);
+        }
+    }
+
+    Ok(ExitCode::from(code))
+}
+
+// Splits a batch into records, one per block of non-blank lines
+fn split_records(text: &str) -> Vec<String> {
+    let mut records = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                records.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        records.push(current.join("\n"));
+    }
+
+    records
+}
+
+fn format_name(document: &MrzDocument) -> &'static str {
+    match document {
+        MrzDocument::TD1(_) => "TD1",
+        MrzDocument::TD2(_) => "TD2",
+        MrzDocument::TD3(_) => "TD3",
+        MrzDocument::MRVA(_) => "MRV-A",
+        MrzDocument::MRVB(_) => "MRV-B",
+        MrzDocument::FrenchIdCard(_) => "French identity card",
+        MrzDocument::DrivingLicence(_) => "driving licence",
+        _ => "custom",
+    }
+}
+
+fn print_record(number: usize, result: &Result<MRZResult, MrzError>) {
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            println!("record {}: error: {}", number, err);
+            println!();
+            return;
+        }
+    };
+
+    println!(
+        "record {}: {} ({:?}), issued by {}: {}, quality {:.2}",
+        number,
+        format_name(&result.document),
+        result.kind,
+        result.issuing_state,
+        if result.is_valid { "valid" } else { "INVALID" },
+        result.quality()
+    );
+    for warning in &result.warnings {
+        println!("  warning: {}", warning);
+    }
+
+    let fields = result.fields();
+    let mut names: Vec<&str> = fields.keys().copied().collect();
+    names.sort_unstable();
+    for name in names {
+        let field = fields[name];
+        let check = if field.check_digit.is_empty() || field.check_digit == "<" {
+            String::new()
+        } else {
+            format!("  [check digit {}: {}]", field.check_digit, if field.is_valid { "ok" } else { "mismatch" })
+        };
+        let confidence = if field.confidence < 1.0 {
+            format!("  (confidence {:.2})", field.confidence)
+        } else {
+            String::new()
+        };
+        println!("  {:<20} {}{}{}", name, field.value, check, confidence);
+    }
+    println!();
+}
+
+fn json_record(number: usize, result: &Result<MRZResult, MrzError>) -> serde_json::Value {
+    match result {
+        Ok(result) => serde_json::json!({
+            "record": number,
+            "quality": result.quality(),
+            "issuing_country": result.issuing_country(),
+            "nationality": result.nationality(),
+            "result": result,
+        }),
+        Err(err) => serde_json::json!({ "record": number, "error": err.to_string() }),
+    }
+}
+
+// generate: Prints the MRZ lines built from the arguments.
+fn generate(args: &GenerateArgs) -> Result<ExitCode, String> {
+    let given_names: Vec<&str> = args.given_names.split_whitespace().collect();
+    let mut builder = MrzBuilder::new(args.format.into())
+        .issuing_state(&args.issuing_state)
+        .names(&args.surname, &given_names)
+        .document_number(&args.document_number)
+        .nationality(&args.nationality)
+        .optional_data(&args.optional_data)
+        .optional_data_2(&args.optional_data_2);
+    if let Some(document_type) = &args.document_type {
+        builder = builder.document_type(document_type);
+    }
+    if let Some(sex) = args.sex {
+        builder = builder.sex(sex);
+    }
+    if let Some(birth_date) = args.birth_date {
+        builder = builder.birth_date(birth_date);
+    }
+    if let Some(expiry_date) = args.expiry_date {
+        builder = builder.expiry_date(expiry_date);
+    }
+
+    for line in builder.build().map_err(|err| err.to_string())? {
+        println!("{}", line);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// check_digit: Prints each value followed by its check digit.
+fn check_digit(values: &[String]) -> Result<ExitCode, String> {
+    for value in values {
+        let digit = calculate_check_digits(value).map_err(|err| format!("{:?}: {}", value, err))?;
+        println!("{} {}", value, digit);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// Reads a YYYY-MM-DD date
+fn parse_date(value: &str) -> Result<MrzDate, String> {
+    let invalid = || format!("invalid date {:?}, expected YYYY-MM-DD", value);
+
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    MrzDate::new(
+        year.parse().map_err(|_| invalid())?,
+        month.parse().map_err(|_| invalid())?,
+        day.parse().map_err(|_| invalid())?,
+    )
+    .ok_or_else(invalid)
+}
+
+// Reads a sex given as M, F or X
+fn parse_sex(value: &str) -> Result<Sex, String> {
+    match value.to_ascii_uppercase().as_str() {
+        "M" => Ok(Sex::Male),
+        "F" => Ok(Sex::Female),
+        "X" => Ok(Sex::Unspecified),
+        _ => Err(format!("invalid sex {:?}, expected M, F or X", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_split_records() {
+        let text = "\nP<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\r\nL898902C36UTO7408122F1204159ZE184226B<<<<<10\n  \n\nI<UTOD231458907<<<<<<<<<<<<<<<\n";
+        assert_eq!(
+            split_records(text),
+            vec![
+                "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\nL898902C36UTO7408122F1204159ZE184226B<<<<<10",
+                "I<UTOD231458907<<<<<<<<<<<<<<<",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("1974-08-12").ok(), MrzDate::new(1974, 8, 12));
+        assert!(parse_date("1974-02-30").is_err());
+        assert!(parse_date("740812").is_err());
+    }
+
+    #[test]
+    fn test_parse_sex() {
+        assert_eq!(parse_sex("f"), Ok(Sex::Female));
+        assert_eq!(parse_sex("X"), Ok(Sex::Unspecified));
+        assert!(parse_sex("<").is_err());
+    }
+}
@@ -1,2 +1,3 @@
+pub mod countries;
 pub mod mrz_utils;
 pub mod mrz_field_name;
@@ -9,3 +9,9 @@ pub const EXPIRY_DATE_FIELD: &str = "expiry_date";
 pub const OPTIONAL_DATA_1_FIELD: &str = "optional_data_1";
 pub const OPTIONAL_DATA_2_FIELD: &str = "optional_data_2";
 pub const FINAL_CHECK_DIGIT_FIELD: &str = "final_check_digit";
+pub const SURNAME_FIELD: &str = "surname";
+pub const GIVEN_NAMES_FIELD: &str = "given_names";
+pub const ISSUING_DEPARTMENT_FIELD: &str = "issuing_department";
+pub const ISSUING_OFFICE_FIELD: &str = "issuing_office";
+pub const ISSUE_DATE_FIELD: &str = "issue_date";
+pub const VERSION_FIELD: &str = "version";
@@ -0,0 +1,114 @@
+use crate::constants::mrz_field_name::{BIRTHDATE_FIELD, DOCUMENT_NUMBER_FIELD, EXPIRY_DATE_FIELD};
+use crate::error::mrz_error::MrzError;
+use crate::parser::parser::MRZResult;
+use sha1::{Digest, Sha1};
+
+// Key derivation counters from ICAO 9303 Part 11, section 9.7.1
+pub(crate) const KDF_COUNTER_ENC: u32 = 1;
+pub(crate) const KDF_COUNTER_MAC: u32 = 2;
+
+// Basic Access Control keys derived from the MRZ (ICAO 9303 Part 11, section 4.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BacKeys {
+    pub k_seed: [u8; 16],
+    pub k_enc: [u8; 16],
+    pub k_mac: [u8; 16],
+}
+
+impl BacKeys {
+    // from_mrz_information: Derives the BAC keys from an MRZ_information string.
+    pub fn from_mrz_information(mrz_information: &str) -> Self {
+        let digest = Sha1::digest(mrz_information.as_bytes());
+        let mut k_seed = [0u8; 16];
+        k_seed.copy_from_slice(&digest[..16]);
+
+        BacKeys {
+            k_seed,
+            k_enc: derive_3des_key(&k_seed, KDF_COUNTER_ENC),
+            k_mac: derive_3des_key(&k_seed, KDF_COUNTER_MAC),
+        }
+    }
+}
+
+impl MRZResult {
+    // mrz_information: Returns the document number, birth date and expiry date, each followed by its
+    // check digit, as used to derive the chip access keys.
+    pub fn mrz_information(&self) -> Result<String, MrzError> {
+        let mut information = String::new();
+
+        for name in [DOCUMENT_NUMBER_FIELD, BIRTHDATE_FIELD, EXPIRY_DATE_FIELD] {
+            let field = self.get(name).ok_or(MrzError::MissingField(name))?;
+            information.push_str(&field.raw_value);
+            information.push_str(&field.check_digit);
+        }
+
+        Ok(information)
+    }
+
+    // bac_keys: Derives the Basic Access Control keys of the document.
+    pub fn bac_keys(&self) -> Result<BacKeys, MrzError> {
+        Ok(BacKeys::from_mrz_information(&self.mrz_information()?))
+    }
+}
+
+// SHA-1 key derivation function: H(K || c), with c as a 32-bit big-endian counter
+pub(crate) fn kdf_sha1(key: &[u8], counter: u32) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update(key);
+    hasher.update(counter.to_be_bytes());
+    hasher.finalize().into()
+}
+
+// Derives a two-key 3DES key (Ka || Kb) with adjusted parity bits
+fn derive_3des_key(seed: &[u8], counter: u32) -> [u8; 16] {
+    let digest = kdf_sha1(seed, counter);
+    let mut key = [0u8; 16];
+    key.copy_from_slice(&digest[..16]);
+    adjust_parity(&mut key);
+    key
+}
+
+// Sets the least significant bit of every byte so that each byte has odd parity, as required for DES keys
+pub(crate) fn adjust_parity(key: &mut [u8]) {
+    for byte in key.iter_mut() {
+        if byte.count_ones() % 2 == 0 {
+            *byte ^= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::mrz_builder::{MrzBuilder, MrzFormat};
+    use crate::parser::mrz_date::MrzDate;
+    use crate::parser::sex::Sex;
+    use crate::MRZParser;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+
+    // Worked example from ICAO 9303 Part 11, Appendix D.1
+    #[test]
+    fn test_bac_keys() {
+        let lines = MrzBuilder::new(MrzFormat::TD3)
+            .issuing_state("UTO")
+            .names("Eriksson", &["Anna", "Maria"])
+            .document_number("L898902C")
+            .nationality("UTO")
+            .birth_date(MrzDate::new(1969, 8, 6).unwrap())
+            .sex(Sex::Female)
+            .expiry_date(MrzDate::new(1994, 6, 23).unwrap())
+            .optional_data("ZE184226B")
+            .build()
+            .unwrap();
+        let result = MRZParser::new_mrz_line_parser(lines).parse().unwrap();
+
+        assert_eq!(result.mrz_information().unwrap(), "L898902C<369080619406236");
+
+        let keys = result.bac_keys().unwrap();
+        assert_eq!(hex(&keys.k_seed), "239AB9CB282DAF66231DC5A4DF6BFBAE");
+        assert_eq!(hex(&keys.k_enc), "AB94FDECF2674FDFB9B391F85D7F76F2");
+        assert_eq!(hex(&keys.k_mac), "7962D9ECE03D1ACD4C76089DCE131543");
+    }
+}
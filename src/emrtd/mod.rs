@@ -0,0 +1,2 @@
+pub mod bac;
+pub mod pace;
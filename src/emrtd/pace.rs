@@ -0,0 +1,114 @@
+use crate::emrtd::bac::{adjust_parity, kdf_sha1};
+use crate::error::mrz_error::MrzError;
+use crate::parser::parser::MRZResult;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+// Key derivation counter for the PACE password key (ICAO 9303 Part 11, section 9.7.3)
+const KDF_COUNTER_PI: u32 = 3;
+
+// Symmetric ciphers of the standard PACE cipher suites
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaceCipher {
+    TripleDes,
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+impl PaceCipher {
+    // key_length: Returns the key length in bytes.
+    pub fn key_length(&self) -> usize {
+        match self {
+            PaceCipher::TripleDes | PaceCipher::Aes128 => 16,
+            PaceCipher::Aes192 => 24,
+            PaceCipher::Aes256 => 32,
+        }
+    }
+}
+
+// pace_mrz_password: Encodes an MRZ_information string as a PACE password, K = SHA-1(MRZ_information).
+pub fn pace_mrz_password(mrz_information: &str) -> [u8; 20] {
+    Sha1::digest(mrz_information.as_bytes()).into()
+}
+
+// derive_k_pi: Derives K_pi from an encoded PACE password for the given cipher.
+//
+// 3DES and AES-128 keys use SHA-1, AES-192 and AES-256 keys use SHA-256, truncated to the key length.
+// The parity bits of 3DES keys are adjusted as for BAC.
+pub fn derive_k_pi(password: &[u8], cipher: PaceCipher) -> Vec<u8> {
+    let mut key = match cipher {
+        PaceCipher::TripleDes | PaceCipher::Aes128 => kdf_sha1(password, KDF_COUNTER_PI).to_vec(),
+        PaceCipher::Aes192 | PaceCipher::Aes256 => {
+            let mut hasher = Sha256::new();
+            hasher.update(password);
+            hasher.update(KDF_COUNTER_PI.to_be_bytes());
+            hasher.finalize().to_vec()
+        }
+    };
+
+    key.truncate(cipher.key_length());
+    if cipher == PaceCipher::TripleDes {
+        adjust_parity(&mut key);
+    }
+    key
+}
+
+impl MRZResult {
+    // pace_password: Returns the encoded PACE password derived from the MRZ.
+    pub fn pace_password(&self) -> Result<[u8; 20], MrzError> {
+        Ok(pace_mrz_password(&self.mrz_information()?))
+    }
+
+    // pace_k_pi: Derives the PACE password key K_pi for the given cipher.
+    pub fn pace_k_pi(&self, cipher: PaceCipher) -> Result<Vec<u8>, MrzError> {
+        Ok(derive_k_pi(&self.pace_password()?, cipher))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::mrz_builder::{MrzBuilder, MrzFormat};
+    use crate::parser::mrz_date::MrzDate;
+    use crate::parser::sex::Sex;
+    use crate::MRZParser;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+
+    // MRZ, password and AES-128 key of the worked example in ICAO 9303 Part 11, Appendix G.1
+    #[test]
+    fn test_pace_keys() {
+        let lines = MrzBuilder::new(MrzFormat::TD1)
+            .document_type("ID")
+            .issuing_state("UTO")
+            .names("Eriksson", &["Anna", "Maria"])
+            .document_number("T22000129")
+            .nationality("UTO")
+            .birth_date(MrzDate::new(1964, 8, 12).unwrap())
+            .sex(Sex::Female)
+            .expiry_date(MrzDate::new(2010, 10, 31).unwrap())
+            .build()
+            .unwrap();
+        let result = MRZParser::new_mrz_line_parser(lines).parse().unwrap();
+        assert_eq!(result.mrz_information().unwrap(), "T22000129364081251010318");
+
+        let password = result.pace_password().unwrap();
+        assert_eq!(hex(&password), "7E2D2A41C74EA0B38CD36F863939BFA8E9032AAD");
+
+        assert_eq!(hex(&result.pace_k_pi(PaceCipher::Aes128).unwrap()), "89DED1B26624EC1E634C1989302849DD");
+
+        // The example has no key for the other ciphers: these are regression values derived from its password
+        assert_eq!(hex(&result.pace_k_pi(PaceCipher::TripleDes).unwrap()), "89DFD0B36725EC1F624C1989312949DC");
+        assert_eq!(
+            hex(&result.pace_k_pi(PaceCipher::Aes192).unwrap()),
+            "D79A23C126202AC9051FEBFBC0E8A03B1C6645D85752B4B7"
+        );
+        assert_eq!(
+            hex(&result.pace_k_pi(PaceCipher::Aes256).unwrap()),
+            "D79A23C126202AC9051FEBFBC0E8A03B1C6645D85752B4B71408FA229AB6D56B"
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod mrz_error;
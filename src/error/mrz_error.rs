@@ -0,0 +1,84 @@
+use crate::parser::strict::Violation;
+use std::error::Error;
+use std::fmt;
+
+// Errors returned while detecting, validating or parsing an MRZ.
+//
+// Line and column positions are zero-based. When the error comes from a standalone value
+// (e.g. `calculate_check_digits`), `line` is 0 and `column` is the offset within that value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MrzError {
+    // The input does not have a number of lines matching any supported MRZ format.
+    InvalidLineCount(usize),
+    // A line does not have the number of characters required by the detected format.
+    InvalidLineLength {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+    // A character outside of the MRZ character set was found.
+    InvalidCharacter {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+    // A date field could not be interpreted as a YYMMDD date.
+    InvalidDate(String),
+    // A sex field holds a character other than `M`, `F`, `X` or `<`.
+    InvalidSex(String),
+    // The input could not be matched to any supported MRZ format.
+    UnknownFormat,
+    // A value is too long for the MRZ field it is written to.
+    FieldTooLong {
+        field: &'static str,
+        max_length: usize,
+        actual: usize,
+    },
+    // The document format does not have a field required by the operation.
+    MissingField(&'static str),
+    // Strict validation rejected the input, with every violation found in line and column order.
+    StrictValidation(Vec<Violation>),
+    // Strict parsing found an issuing state or nationality missing from the ICAO 9303 country code table.
+    UnknownCode {
+        field: &'static str,
+        code: String,
+    },
+}
+
+impl fmt::Display for MrzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MrzError::InvalidLineCount(count) => write!(f, "invalid mrz line count: {}", count),
+            MrzError::InvalidLineLength { line, expected, actual } => {
+                write!(f, "invalid mrz line length on line {}: expected {}, got {}", line, expected, actual)
+            }
+            MrzError::InvalidCharacter {
+                line,
+                column,
+                character,
+            } => {
+                write!(f, "invalid mrz character {:?} at line {}, column {}", character, line, column)
+            }
+            MrzError::InvalidDate(value) => write!(f, "invalid mrz date: {:?}", value),
+            MrzError::InvalidSex(value) => write!(f, "invalid mrz sex: {:?}", value),
+            MrzError::UnknownFormat => write!(f, "unknown mrz format"),
+            MrzError::FieldTooLong {
+                field,
+                max_length,
+                actual,
+            } => write!(f, "mrz field {} is too long: at most {} characters, got {}", field, max_length, actual),
+            MrzError::MissingField(field) => write!(f, "mrz field {} is missing", field),
+            MrzError::StrictValidation(violations) => {
+                write!(f, "mrz failed strict validation:")?;
+                for (idx, violation) in violations.iter().enumerate() {
+                    let separator = if idx == 0 { " " } else { "; " };
+                    write!(f, "{}{}", separator, violation)?;
+                }
+                Ok(())
+            }
+            MrzError::UnknownCode { field, code } => write!(f, "unknown mrz {} code {:?}", field, code),
+        }
+    }
+}
+
+impl Error for MrzError {}
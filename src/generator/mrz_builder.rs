@@ -0,0 +1,458 @@
+use crate::constants::mrz_field_name::{
+    COUNTRY_CODE_FIELD, DOCUMENT_NUMBER_FIELD, DOCUMENT_TYPE_FIELD, NATIONALITY_FIELD, OPTIONAL_DATA_1_FIELD,
+    OPTIONAL_DATA_2_FIELD,
+};
+use crate::constants::mrz_utils::{
+    TYPE1_NUMBER_OF_CHARACTERS_PER_LINE, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+};
+use crate::error::mrz_error::MrzError;
+use crate::parser::mrz_date::MrzDate;
+use crate::parser::sex::Sex;
+use crate::utils::utils::calculate_check_digits;
+
+// The document layouts the builder can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MrzFormat {
+    TD1,
+    TD2,
+    TD3,
+    MRVA,
+    MRVB,
+}
+
+impl MrzFormat {
+    fn line_length(&self) -> usize {
+        match self {
+            MrzFormat::TD1 => TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
+            MrzFormat::TD2 | MrzFormat::MRVB => TYPE2_NUMBER_OF_CHARACTERS_PER_LINE,
+            MrzFormat::TD3 | MrzFormat::MRVA => TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+        }
+    }
+
+    fn name_length(&self) -> usize {
+        match self {
+            MrzFormat::TD1 => TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
+            _ => self.line_length() - 5,
+        }
+    }
+}
+
+// Builds the MRZ lines of a document from its structured data.
+//
+// Every value is upper-cased and padded with fillers, and all check digits are computed.
+// Names that do not fit are truncated following ICAO 9303: secondary identifiers are cut first,
+// and the primary identifier is shortened only when there is no room left for an initial.
+#[derive(Debug, Clone)]
+pub struct MrzBuilder {
+    format: MrzFormat,
+    document_type: String,
+    issuing_state: String,
+    primary_identifier: String,
+    secondary_identifiers: Vec<String>,
+    document_number: String,
+    nationality: String,
+    birth_date: Option<MrzDate>,
+    sex: Option<Sex>,
+    expiry_date: Option<MrzDate>,
+    optional_data_1: String,
+    optional_data_2: String,
+}
+
+impl MrzBuilder {
+    pub fn new(format: MrzFormat) -> Self {
+        let document_type = match format {
+            MrzFormat::TD1 | MrzFormat::TD2 => "I",
+            MrzFormat::TD3 => "P",
+            MrzFormat::MRVA | MrzFormat::MRVB => "V",
+        };
+
+        MrzBuilder {
+            format,
+            document_type: document_type.to_string(),
+            issuing_state: String::new(),
+            primary_identifier: String::new(),
+            secondary_identifiers: Vec::new(),
+            document_number: String::new(),
+            nationality: String::new(),
+            birth_date: None,
+            sex: None,
+            expiry_date: None,
+            optional_data_1: String::new(),
+            optional_data_2: String::new(),
+        }
+    }
+
+    pub fn document_type(mut self, document_type: &str) -> Self {
+        self.document_type = document_type.to_string();
+        self
+    }
+
+    pub fn issuing_state(mut self, issuing_state: &str) -> Self {
+        self.issuing_state = issuing_state.to_string();
+        self
+    }
+
+    // names: Sets the primary identifier (surname) and the secondary identifiers (given names).
+    pub fn names(mut self, primary: &str, secondary: &[&str]) -> Self {
+        self.primary_identifier = primary.to_string();
+        self.secondary_identifiers = secondary.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    pub fn document_number(mut self, document_number: &str) -> Self {
+        self.document_number = document_number.to_string();
+        self
+    }
+
+    pub fn nationality(mut self, nationality: &str) -> Self {
+        self.nationality = nationality.to_string();
+        self
+    }
+
+    // birth_date: Sets the birth date. Unknown parts of a partial date are written as fillers.
+    pub fn birth_date(mut self, birth_date: MrzDate) -> Self {
+        self.birth_date = Some(birth_date);
+        self
+    }
+
+    // sex: Sets the sex. Sex::Unspecified is written as `X`, and a filler is written when the sex is not set.
+    pub fn sex(mut self, sex: Sex) -> Self {
+        self.sex = Some(sex);
+        self
+    }
+
+    // expiry_date: Sets the expiry date. Unknown parts of a partial date are written as fillers.
+    pub fn expiry_date(mut self, expiry_date: MrzDate) -> Self {
+        self.expiry_date = Some(expiry_date);
+        self
+    }
+
+    // optional_data: Sets the optional data of the first line for TD1, or the personal number / optional
+    // data of the second line for the other formats.
+    pub fn optional_data(mut self, optional_data: &str) -> Self {
+        self.optional_data_1 = optional_data.to_string();
+        self
+    }
+
+    // optional_data_2: Sets the optional data of the second line of a TD1 document.
+    pub fn optional_data_2(mut self, optional_data: &str) -> Self {
+        self.optional_data_2 = optional_data.to_string();
+        self
+    }
+
+    // TD1 document numbers longer than 9 characters continue in the optional data: the check digit position
+    // holds `<`, and the rest of the number is followed by its check digit and a filler.
+    fn document_number_and_optional_data(&self) -> Result<(String, String), MrzError> {
+        let is_long = self.format == MrzFormat::TD1 && self.document_number.trim().len() > 9;
+        if !is_long {
+            let document_number = with_check_digit(&pad(&self.document_number, 9, DOCUMENT_NUMBER_FIELD)?)?;
+            return Ok((document_number, self.optional_data_1.clone()));
+        }
+
+        let number = pad(&self.document_number, 22, DOCUMENT_NUMBER_FIELD)?;
+        let number = with_check_digit(number.trim_end_matches('<'))?;
+        Ok((format!("{}<", &number[..9]), format!("{}<{}", &number[9..], self.optional_data_1)))
+    }
+
+    // build: Returns the MRZ lines.
+    pub fn build(&self) -> Result<Vec<String>, MrzError> {
+        let document_type = pad(&self.document_type, 2, DOCUMENT_TYPE_FIELD)?;
+        let issuing_state = pad(&self.issuing_state, 3, COUNTRY_CODE_FIELD)?;
+        let nationality = pad(&self.nationality, 3, NATIONALITY_FIELD)?;
+        let (document_number, optional_data_1) = self.document_number_and_optional_data()?;
+        let birth_date = with_check_digit(&date(self.birth_date))?;
+        let expiry_date = with_check_digit(&date(self.expiry_date))?;
+        let names = names(&self.primary_identifier, &self.secondary_identifiers, self.format.name_length())?;
+
+        let sex = self.sex.map_or('<', Sex::to_mrz);
+
+        let lines = match self.format {
+            MrzFormat::TD1 => {
+                let first = format!(
+                    "{}{}{}{}",
+                    document_type,
+                    issuing_state,
+                    document_number,
+                    pad(&optional_data_1, 15, OPTIONAL_DATA_1_FIELD)?
+                );
+                let mut second = format!(
+                    "{}{}{}{}{}",
+                    birth_date,
+                    sex,
+                    expiry_date,
+                    nationality,
+                    pad(&self.optional_data_2, 11, OPTIONAL_DATA_2_FIELD)?
+                );
+                let composite = format!("{}{}{}{}", &first[5..], &second[..7], &second[8..15], &second[18..]);
+                second.push_str(&calculate_check_digits(&composite)?);
+                vec![first, second, names]
+            }
+            MrzFormat::TD2 | MrzFormat::TD3 => {
+                let optional_length = if self.format == MrzFormat::TD2 { 7 } else { 14 };
+                let optional_data = pad(&self.optional_data_1, optional_length, OPTIONAL_DATA_1_FIELD)?;
+                let optional_data = if self.format == MrzFormat::TD2 {
+                    optional_data
+                } else if optional_data.trim_matches('<').is_empty() {
+                    format!("{}<", optional_data)
+                } else {
+                    with_check_digit(&optional_data)?
+                };
+
+                let mut second =
+                    format!("{}{}{}{}{}{}", document_number, nationality, birth_date, sex, expiry_date, optional_data);
+                let composite = format!("{}{}{}", &second[..10], &second[13..20], &second[21..]);
+                second.push_str(&calculate_check_digits(&composite)?);
+                vec![format!("{}{}{}", document_type, issuing_state, names), second]
+            }
+            MrzFormat::MRVA | MrzFormat::MRVB => {
+                let optional_length = if self.format == MrzFormat::MRVB { 8 } else { 16 };
+                let second = format!(
+                    "{}{}{}{}{}{}",
+                    document_number,
+                    nationality,
+                    birth_date,
+                    sex,
+                    expiry_date,
+                    pad(&self.optional_data_1, optional_length, OPTIONAL_DATA_1_FIELD)?
+                );
+                vec![format!("{}{}{}", document_type, issuing_state, names), second]
+            }
+        };
+
+        Ok(lines)
+    }
+}
+
+// Upper-cases the value, checks it only holds MRZ characters and right-pads it with fillers
+fn pad(value: &str, length: usize, field: &'static str) -> Result<String, MrzError> {
+    let value = value.trim().to_uppercase().replace(' ', "<");
+    if let Some((column, character)) = value
+        .chars()
+        .enumerate()
+        .find(|(_, ch)| !ch.is_ascii_uppercase() && !ch.is_ascii_digit() && *ch != '<')
+    {
+        return Err(MrzError::InvalidCharacter {
+            line: 0,
+            column,
+            character,
+        });
+    }
+    if value.len() > length {
+        return Err(MrzError::FieldTooLong {
+            field,
+            max_length: length,
+            actual: value.len(),
+        });
+    }
+
+    Ok(format!("{:<<width$}", value, width = length))
+}
+
+fn with_check_digit(value: &str) -> Result<String, MrzError> {
+    Ok(format!("{}{}", value, calculate_check_digits(value)?))
+}
+
+// Writes a date as YYMMDD, with fillers for the unknown parts
+fn date(date: Option<MrzDate>) -> String {
+    let date = date.unwrap_or(MrzDate {
+        year: None,
+        month: None,
+        day: None,
+    });
+
+    [date.year.map(|year| (year % 100) as u8), date.month, date.day]
+        .iter()
+        .map(|part| match part {
+            Some(value) => format!("{:02}", value),
+            None => "<<".to_string(),
+        })
+        .collect()
+}
+
+// Converts a name to MRZ characters: spaces, hyphens and commas become fillers, apostrophes are dropped
+fn name_component(name: &str) -> Result<String, MrzError> {
+    let converted: String = name
+        .trim()
+        .to_uppercase()
+        .chars()
+        .filter(|ch| *ch != '\'')
+        .map(|ch| if matches!(ch, ' ' | '-' | ',') { '<' } else { ch })
+        .collect();
+
+    if let Some((column, character)) = converted
+        .chars()
+        .enumerate()
+        .find(|(_, ch)| !ch.is_ascii_uppercase() && *ch != '<')
+    {
+        return Err(MrzError::InvalidCharacter {
+            line: 0,
+            column,
+            character,
+        });
+    }
+
+    Ok(converted)
+}
+
+// Writes the name field, truncating it to the available length when needed
+fn names(primary: &str, secondary: &[String], length: usize) -> Result<String, MrzError> {
+    let primary = name_component(primary)?;
+    let secondary = secondary
+        .iter()
+        .map(|name| name_component(name))
+        .filter(|name| !matches!(name, Ok(name) if name.is_empty()))
+        .collect::<Result<Vec<String>, MrzError>>()?
+        .join("<");
+
+    let mut name = if secondary.is_empty() {
+        primary.clone()
+    } else if primary.len() + 3 > length {
+        // No room for an initial: shorten the primary identifier
+        format!("{}<<{}", &primary[..length - 3], &secondary[..1])
+    } else {
+        format!("{}<<{}", primary, secondary)
+    };
+
+    name.truncate(length);
+    Ok(format!("{:<<width$}", name, width = length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::mrz_field_name::NAME_FIELD;
+    use crate::MRZParser;
+
+    fn specimen(format: MrzFormat) -> MrzBuilder {
+        MrzBuilder::new(format)
+            .issuing_state("UTO")
+            .names("Eriksson", &["Anna", "Maria"])
+            .nationality("UTO")
+            .birth_date(MrzDate::new(1974, 8, 12).unwrap())
+            .sex(Sex::Female)
+            .expiry_date(MrzDate::new(2012, 4, 15).unwrap())
+    }
+
+    #[test]
+    fn test_build_td1() {
+        let lines = specimen(MrzFormat::TD1)
+            .document_number("D23145890")
+            .build()
+            .unwrap();
+        assert_eq!(
+            lines,
+            vec!["I<UTOD231458907<<<<<<<<<<<<<<<", "7408122F1204159UTO<<<<<<<<<<<6", "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",]
+        );
+    }
+
+    // Long document number example from ICAO 9303 Part 5, Appendix B
+    #[test]
+    fn test_build_td1_long_document_number() {
+        let lines = specimen(MrzFormat::TD1)
+            .document_number("D23145890734")
+            .build()
+            .unwrap();
+        assert_eq!(lines[0], "I<UTOD23145890<7349<<<<<<<<<<<");
+
+        let result = MRZParser::new_mrz_line_parser(lines).parse().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.get(DOCUMENT_NUMBER_FIELD).unwrap().value, "D23145890734");
+    }
+
+    #[test]
+    fn test_build_td2() {
+        let lines = specimen(MrzFormat::TD2)
+            .document_number("D23145890")
+            .build()
+            .unwrap();
+        assert_eq!(lines, vec!["I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<", "D231458907UTO7408122F1204159<<<<<<<6"]);
+    }
+
+    #[test]
+    fn test_build_td3() {
+        let lines = specimen(MrzFormat::TD3)
+            .document_number("L898902C3")
+            .optional_data("ZE184226B")
+            .build()
+            .unwrap();
+        assert_eq!(
+            lines,
+            vec!["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<10",]
+        );
+    }
+
+    #[test]
+    fn test_build_visas() {
+        let visa = |format: MrzFormat| {
+            specimen(format)
+                .document_number("L8988901C")
+                .nationality("XXX")
+                .birth_date(MrzDate::new(1940, 9, 7).unwrap())
+                .expiry_date(MrzDate::new(1996, 12, 10).unwrap())
+        };
+        assert_eq!(
+            visa(MrzFormat::MRVB).build().unwrap(),
+            vec!["V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<", "L8988901C4XXX4009078F9612109<<<<<<<<"]
+        );
+        assert_eq!(
+            visa(MrzFormat::MRVA)
+                .optional_data("6ZE184226B")
+                .build()
+                .unwrap(),
+            vec!["V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L8988901C4XXX4009078F96121096ZE184226B<<<<<<",]
+        );
+    }
+
+    #[test]
+    fn test_name_truncation() {
+        let name = names(
+            "Nilavadhanananda",
+            &["Chayapa Dejthamrong Krasuang".to_string()],
+            TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
+        )
+        .unwrap();
+        assert_eq!(name, "NILAVADHANANANDA<<CHAYAPA<DEJT");
+
+        let name = names("Gutierrez-Hernandez-Villanueva-Obregon", &["Ana".to_string()], 31).unwrap();
+        assert_eq!(name, "GUTIERREZ<HERNANDEZ<VILLANUE<<A");
+
+        let name = names("O'Brien", &["Mary-Jane".to_string()], 31).unwrap();
+        assert_eq!(name, "OBRIEN<<MARY<JANE<<<<<<<<<<<<<<");
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let result = specimen(MrzFormat::TD3)
+            .document_number("L898902C36789")
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            MrzError::FieldTooLong {
+                field: DOCUMENT_NUMBER_FIELD,
+                max_length: 9,
+                actual: 13
+            }
+        );
+
+        let result = specimen(MrzFormat::TD3)
+            .document_number("L898#02C3")
+            .build();
+        assert!(matches!(result, Err(MrzError::InvalidCharacter { character: '#', .. })));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for format in [MrzFormat::TD1, MrzFormat::TD2, MrzFormat::TD3, MrzFormat::MRVA, MrzFormat::MRVB] {
+            let lines = specimen(format)
+                .document_number("AB1234567")
+                .optional_data("X12")
+                .build()
+                .unwrap();
+
+            let result = MRZParser::new_mrz_line_parser(lines).parse().unwrap();
+            assert!(result.is_valid, "{:?}", format);
+            assert_eq!(result.fields()[DOCUMENT_NUMBER_FIELD].value, "AB1234567");
+            assert_eq!(result.fields()[NAME_FIELD].value, "ERIKSSON ANNA MARIA");
+        }
+    }
+}
@@ -1,47 +1,80 @@
 use crate::constants::mrz_utils::{
-    MRZ_TYPE1, MRZ_TYPE2, MRZ_TYPE3, TYPE1_NUMBER_OF_CHARACTERS_PER_LINE, TYPE1_TOTAL_NUMBER_OF_CHARACTERS,
-    TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, TYPE2_TOTAL_NUMBER_OF_CHARACTERS, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+    DRIVING_LICENCE_NUMBER_OF_CHARACTERS_PER_LINE, FRENCH_ID_PREFIX, MRZ_TYPE1, MRZ_TYPE2, MRZ_TYPE3,
+    MRZ_TYPE_DRIVING_LICENCE, MRZ_TYPE_FRENCH_ID, MRZ_TYPE_MRVA, MRZ_TYPE_MRVB, TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
+    TYPE1_TOTAL_NUMBER_OF_CHARACTERS, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, TYPE2_TOTAL_NUMBER_OF_CHARACTERS,
+    TYPE3_NUMBER_OF_CHARACTERS_PER_LINE, TYPE3_TOTAL_NUMBER_OF_CHARACTERS,
 };
-use crate::parser::parser::{IMRZParser, MRZResult};
-use crate::parser::td1::TD1;
-use crate::parser::td2::TD2;
-use crate::parser::td3::TD3;
-use crate::utils::utils::check_same;
+use crate::parser::parser::check_lines;
+use crate::parser::repair::check_repairable_lines;
 
+#[allow(clippy::module_inception)]
 mod utils;
+#[allow(clippy::module_inception)]
 mod parser;
 pub mod constants;
+mod emrtd;
+pub mod error;
+mod generator;
+mod scanner;
+
+pub use crate::constants::countries::{lookup_country, lookup_country_by_alpha2, Country};
+pub use crate::constants::mrz_utils::{lookup_code, CodeCategory, IcaoCode};
+pub use crate::emrtd::bac::BacKeys;
+pub use crate::emrtd::pace::{derive_k_pi, pace_mrz_password, PaceCipher};
+pub use crate::error::mrz_error::MrzError;
+pub use crate::generator::mrz_builder::{MrzBuilder, MrzFormat};
+pub use crate::parser::correction::{
+    CheckDigitCorrector, CheckedValue, CorrectionOutcome, FieldCorrection, Substitution,
+};
+pub use crate::parser::document::{
+    DocumentKind, DrivingLicenceDocument, FrenchIdCardDocument, MRVDocument, MrzDocument, TD1Document, TD2Document,
+    TD3Document,
+};
+pub use crate::parser::driving_licence::DrivingLicence;
+pub use crate::parser::french_id_card::FrenchIdCard;
+pub use crate::parser::mrv::{MRVA, MRVB};
+pub use crate::parser::mrz_date::{CenturyPivot, DateOptions, MrzDate};
+pub use crate::parser::mrz_field::{MrzField, ParsedValue};
+pub use crate::parser::names::Names;
+pub use crate::parser::ocr::{Alternative, OcrInput, OcrReading};
+pub use crate::parser::options::{CorrectionMode, ParserOptions};
+pub use crate::parser::parser::{IMRZParser, MRZResult};
+pub use crate::parser::repair::{LengthEdit, LineRepair};
+pub use crate::parser::sex::Sex;
+pub use crate::parser::strict::{Violation, ViolationKind};
+pub use crate::parser::td1::TD1;
+pub use crate::parser::td2::TD2;
+pub use crate::parser::td3::TD3;
+pub use crate::parser::warning::ParseWarning;
+pub use crate::scanner::mrz_scanner::{find_mrz_candidates, MrzCandidate};
+pub use crate::utils::utils::{calculate_check_digits, is_value_valid};
+
+// Commonly used types, importable in one go with `use rs_mrz_parser::prelude::*;`
+pub mod prelude {
+    pub use crate::{
+        CorrectionMode, DocumentKind, DrivingLicence, FrenchIdCard, IMRZParser, MRZParser, MRZResult, MrzBuilder,
+        MrzDate, MrzDocument, MrzError, MrzField, MrzFormat, Names, ParserOptions, Sex, MRVA, MRVB, TD1, TD2, TD3,
+    };
+}
 
 pub struct MRZParser {
     mrz_type: usize,
     components: Vec<String>,
+    options: ParserOptions,
 }
 
 impl MRZParser {
-    // Create a new MRZParser from a single MRZ string with lines separated by a newline character
+    // Create a new MRZParser from a single MRZ string, either with lines separated by line breaks or
+    // concatenated. Errors in the input are reported when parsing.
     pub fn new_mrz_string_parser(mrz_str: &str) -> Self {
-        let mrz_type: usize = 0;
-        let components: Vec<String> = if mrz_str.contains('\n') {
-            mrz_str.lines().map(String::from).collect()
-        } else if mrz_str.len() == TYPE1_TOTAL_NUMBER_OF_CHARACTERS {
-            vec![
-                mrz_str[..TYPE1_NUMBER_OF_CHARACTERS_PER_LINE].to_string(),
-                mrz_str[TYPE1_NUMBER_OF_CHARACTERS_PER_LINE..2 * TYPE1_NUMBER_OF_CHARACTERS_PER_LINE].to_string(),
-                mrz_str[2 * TYPE1_NUMBER_OF_CHARACTERS_PER_LINE..].to_string(),
-            ]
-        } else if mrz_str.len() == TYPE2_TOTAL_NUMBER_OF_CHARACTERS {
-            vec![
-                mrz_str[..TYPE2_NUMBER_OF_CHARACTERS_PER_LINE].to_string(),
-                mrz_str[TYPE2_NUMBER_OF_CHARACTERS_PER_LINE..].to_string(),
-            ]
-        } else {
-            vec![
-                mrz_str[..TYPE3_NUMBER_OF_CHARACTERS_PER_LINE].to_string(),
-                mrz_str[TYPE3_NUMBER_OF_CHARACTERS_PER_LINE..].to_string(),
-            ]
-        };
+        MRZParser::new_mrz_line_parser(split_mrz_string(mrz_str))
+    }
 
-        MRZParser { mrz_type, components }
+    // Create a new MRZParser from a single MRZ string, failing if the lines do not match a supported format
+    pub fn try_new_mrz_string_parser(mrz_str: &str) -> Result<Self, MrzError> {
+        let mut parser = MRZParser::new_mrz_string_parser(mrz_str);
+        parser.validate()?;
+        Ok(parser)
     }
 
     // Create a new MRZParser from a vector of MRZ lines
@@ -50,71 +83,119 @@ impl MRZParser {
         MRZParser {
             mrz_type,
             components: mrz_lines,
+            options: ParserOptions::default(),
         }
     }
 
+    // Set the options passed to the document parsers
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     // Return the MRZ type
-    pub fn get_mrz_type(&mut self) -> Result<usize, &'static str> {
+    pub fn get_mrz_type(&mut self) -> Result<usize, MrzError> {
         self.validate()?;
         Ok(self.mrz_type)
     }
 
     // Parse the MRZ information
-    pub fn parse(&mut self) -> Result<MRZResult, &'static str> {
+    pub fn parse(&mut self) -> Result<MRZResult, MrzError> {
         self.validate()?;
 
         let mrz_parser: Box<dyn IMRZParser> = match self.mrz_type {
-            MRZ_TYPE1 => Box::new(TD1::new()),
-            MRZ_TYPE2 => Box::new(TD2::new()),
-            MRZ_TYPE3 => Box::new(TD3::new()),
-            _ => return Err("invalid mrz type"),
+            MRZ_TYPE1 => Box::new(TD1::with_options(self.options.clone())),
+            MRZ_TYPE2 => Box::new(TD2::with_options(self.options.clone())),
+            MRZ_TYPE3 => Box::new(TD3::with_options(self.options.clone())),
+            MRZ_TYPE_MRVA => Box::new(MRVA::with_options(self.options.clone())),
+            MRZ_TYPE_MRVB => Box::new(MRVB::with_options(self.options.clone())),
+            MRZ_TYPE_FRENCH_ID => Box::new(FrenchIdCard::with_options(self.options.clone())),
+            MRZ_TYPE_DRIVING_LICENCE => Box::new(DrivingLicence::with_options(self.options.clone())),
+            _ => return Err(MrzError::UnknownFormat),
         };
 
         mrz_parser.parse(&self.components)
     }
 
+    // Parse the MRZ lines with a caller-provided parser, skipping the built-in format detection
+    pub fn parse_with(&self, mrz_parser: &dyn IMRZParser) -> Result<MRZResult, MrzError> {
+        mrz_parser.parse(&self.components)
+    }
+
     // Validate the input MRZ for formatting errors
-    fn validate(&mut self) -> Result<(), &'static str> {
-        let mut mrz_type: usize = 0;
-
-        match self.components.len() {
-            3 => {
-                for line in &self.components {
-                    if line.len() != TYPE1_NUMBER_OF_CHARACTERS_PER_LINE {
-                        return Err("invalid TD1 format line length");
-                    }
-                }
-                mrz_type = MRZ_TYPE1;
-            }
+    fn validate(&mut self) -> Result<(), MrzError> {
+        let expected = match self.components.len() {
+            1 => DRIVING_LICENCE_NUMBER_OF_CHARACTERS_PER_LINE,
+            3 => TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
             2 => {
-                let mut character_count = Vec::new();
-                for line in &self.components {
-                    if line.len() != TYPE2_NUMBER_OF_CHARACTERS_PER_LINE
-                        && line.len() != TYPE3_NUMBER_OF_CHARACTERS_PER_LINE
-                    {
-                        return Err("invalid mrz line length");
-                    }
-                    character_count.push(line.len());
-                }
-
-                if check_same(&character_count) && character_count[0] == TYPE2_NUMBER_OF_CHARACTERS_PER_LINE {
-                    mrz_type = MRZ_TYPE2;
-                }
-                if check_same(&character_count) && character_count[0] == TYPE3_NUMBER_OF_CHARACTERS_PER_LINE {
-                    mrz_type = MRZ_TYPE3;
+                // The first line decides between TD2 and TD3, the second line must then match it
+                let first = self.components[0].chars().count();
+                if first == TYPE2_NUMBER_OF_CHARACTERS_PER_LINE || first == TYPE3_NUMBER_OF_CHARACTERS_PER_LINE {
+                    first
+                } else if first < (TYPE2_NUMBER_OF_CHARACTERS_PER_LINE + TYPE3_NUMBER_OF_CHARACTERS_PER_LINE) / 2 {
+                    TYPE2_NUMBER_OF_CHARACTERS_PER_LINE
+                } else {
+                    TYPE3_NUMBER_OF_CHARACTERS_PER_LINE
                 }
             }
-            _ => return Err("invalid mrz line length"),
+            count => return Err(MrzError::InvalidLineCount(count)),
+        };
+
+        if self.options.repair_line_length {
+            check_repairable_lines(&self.components, self.components.len(), expected)?;
+        } else {
+            check_lines(&self.components, self.components.len(), expected)?;
         }
 
-        self.mrz_type = mrz_type;
+        // Two-line documents starting with `V` are visas, and 36 character ones starting with `IDFRA` are
+        // French identity cards
+        let first_line = &self.components[0];
+        let is_visa = self.components.len() == 2 && first_line.starts_with('V');
+        self.mrz_type = match (expected, is_visa) {
+            _ if self.components.len() == 1 => MRZ_TYPE_DRIVING_LICENCE,
+            (TYPE1_NUMBER_OF_CHARACTERS_PER_LINE, _) => MRZ_TYPE1,
+            (TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, true) => MRZ_TYPE_MRVB,
+            (TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, false) if first_line.starts_with(FRENCH_ID_PREFIX) => {
+                MRZ_TYPE_FRENCH_ID
+            }
+            (TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, false) => MRZ_TYPE2,
+            (_, false) => MRZ_TYPE3,
+            (_, true) => MRZ_TYPE_MRVA,
+        };
         Ok(())
     }
 }
 
+// Splits an MRZ string into its lines. Line breaks (`\n`, `\r\n` or `\r`), surrounding whitespace and blank
+// lines are ignored. A single line with the total length of a TD1, TD2 or TD3 MRZ is cut into lines.
+fn split_mrz_string(mrz_str: &str) -> Vec<String> {
+    let lines: Vec<&str> = mrz_str
+        .split(['\r', '\n'])
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if let [line] = lines[..] {
+        let chars: Vec<char> = line.chars().collect();
+        let line_length = match chars.len() {
+            TYPE1_TOTAL_NUMBER_OF_CHARACTERS => TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
+            TYPE2_TOTAL_NUMBER_OF_CHARACTERS => TYPE2_NUMBER_OF_CHARACTERS_PER_LINE,
+            TYPE3_TOTAL_NUMBER_OF_CHARACTERS => TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+            _ => return vec![line.to_string()],
+        };
+        return chars
+            .chunks(line_length)
+            .map(|chunk| chunk.iter().collect())
+            .collect();
+    }
+
+    lines.into_iter().map(String::from).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_td1_vec() {
@@ -126,7 +207,7 @@ mod tests {
 
         let mut parser = MRZParser::new_mrz_line_parser(mrz_string);
         let result = parser.parse().unwrap();
-        assert_eq!(result.is_valid, true);
+        assert!(result.is_valid);
         println!("{:?}", result)
     }
 
@@ -138,7 +219,316 @@ mod tests {
         ERIKSSON<<ANNA<MARIA<<<<<<<<<<";
         let mut parser = MRZParser::new_mrz_string_parser(mrz_string);
         let result = parser.parse().unwrap();
-        assert_eq!(result.is_valid, true);
+        assert!(result.is_valid);
         println!("{:?}", result)
     }
+
+    #[test]
+    fn test_parse_with_custom_parser() {
+        struct FirstLineParser;
+
+        impl IMRZParser for FirstLineParser {
+            fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+                let line = input.first().ok_or(MrzError::InvalidLineCount(0))?;
+                let mut fields = HashMap::new();
+                fields.insert("line".to_string(), MrzField::new(line.clone(), line.clone(), String::new(), true));
+
+                Ok(MRZResult {
+                    is_visa: false,
+                    kind: DocumentKind::Other,
+                    is_valid: true,
+                    document: MrzDocument::Custom(fields),
+                    issuing_state: "Unknown".to_string(),
+                    repairs: Vec::new(),
+                    warnings: Vec::new(),
+                })
+            }
+        }
+
+        let parser = MRZParser::new_mrz_line_parser(vec!["ABC".to_string()]);
+        let result = parser.parse_with(&FirstLineParser).unwrap();
+        assert_eq!(result.get("line").unwrap().value, "ABC");
+    }
+
+    #[test]
+    fn test_invalid_line_count() {
+        let mut parser = MRZParser::new_mrz_line_parser(vec!["I<UTOD231458907<<<<<<<<<<<<<<<".to_string(); 4]);
+        assert_eq!(parser.parse().unwrap_err(), MrzError::InvalidLineCount(4));
+    }
+
+    #[test]
+    fn test_invalid_line_length() {
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F1204159ZE184226B<<<<10".to_string(),
+        ];
+        let mut parser = MRZParser::new_mrz_line_parser(mrz_string);
+        assert_eq!(
+            parser.get_mrz_type().unwrap_err(),
+            MrzError::InvalidLineLength {
+                line: 1,
+                expected: 44,
+                actual: 43
+            }
+        );
+    }
+
+    #[test]
+    fn test_repair_line_length() {
+        let repair = ParserOptions {
+            repair_line_length: true,
+            ..ParserOptions::default()
+        };
+        let mrz_string: Vec<String> = vec![
+            "I<UTOD231458907<<<<<<<<<<<<<<<<".to_string(),
+            "7408122F1204159UTO<<<<<<<<<<6".to_string(),
+            "ERIKSSON<<ANNA<MARIA<<<<<<<<<<".to_string(),
+        ];
+
+        let mut parser = MRZParser::new_mrz_line_parser(mrz_string.clone()).with_options(repair);
+        let result = parser.parse().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(
+            result
+                .repairs
+                .iter()
+                .map(|repair| repair.line)
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(result.repairs[1].edits, vec![LengthEdit::Inserted { column: 18 }]);
+
+        let mut parser = MRZParser::new_mrz_line_parser(mrz_string);
+        assert_eq!(
+            parser.parse().unwrap_err(),
+            MrzError::InvalidLineLength {
+                line: 0,
+                expected: 30,
+                actual: 31
+            }
+        );
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let strict = ParserOptions {
+            strict: true,
+            ..ParserOptions::default()
+        };
+        let valid = vec![
+            "I<UTOD231458907<<<<<<<<<<<<<<<".to_string(),
+            "7408122F1204159UTO<<<<<<<<<<<6".to_string(),
+            "ERIKSSON<<ANNA<MARIA<<<<<<<<<<".to_string(),
+        ];
+        assert!(MRZParser::new_mrz_line_parser(valid)
+            .with_options(strict.clone())
+            .parse()
+            .is_ok());
+
+        // Lenient parsing accepts lowercase text and digits in names, strict parsing lists each violation
+        let invalid = vec![
+            "I<UTOd231458907<<<<<<<<<<<<<<<".to_string(),
+            "7408122F1204159UTO<<<<<<<<<<<6".to_string(),
+            "ERIKSS0N<<ANNA<MARIA<<<<<<<<<<".to_string(),
+        ];
+        assert!(MRZParser::new_mrz_line_parser(invalid.clone())
+            .parse()
+            .is_ok());
+        let err = MRZParser::new_mrz_line_parser(invalid)
+            .with_options(strict)
+            .parse()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MrzError::StrictValidation(vec![
+                Violation {
+                    line: 0,
+                    column: 5,
+                    kind: ViolationKind::InvalidCharacter('d'),
+                },
+                Violation {
+                    line: 2,
+                    column: 6,
+                    kind: ViolationKind::UnexpectedCharacter('0'),
+                },
+            ])
+        );
+        assert_eq!(
+            err.to_string(),
+            "mrz failed strict validation: invalid character 'd' at line 0, column 5; \
+             unexpected character '0' at line 2, column 6"
+        );
+    }
+
+    #[test]
+    fn test_string_parser_line_breaks() {
+        let mrz_string = "\r\n  P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<  \r\n\r\n\
+                          L898902C36UTO7408122F1204159ZE184226B<<<<<10\t\r\n";
+        let mut parser = MRZParser::try_new_mrz_string_parser(mrz_string).unwrap();
+        assert_eq!(parser.get_mrz_type().unwrap(), MRZ_TYPE3);
+        assert!(parser.parse().unwrap().is_valid);
+
+        let concatenated = "I<UTOD231458907<<<<<<<<<<<<<<<7408122F1204159UTO<<<<<<<<<<<6ERIKSSON<<ANNA<MARIA<<<<<<<<<<";
+        let mut parser = MRZParser::try_new_mrz_string_parser(concatenated).unwrap();
+        assert_eq!(parser.get_mrz_type().unwrap(), MRZ_TYPE1);
+    }
+
+    #[test]
+    fn test_string_parser_invalid_input() {
+        for input in ["", "\n\n", " \r\n "] {
+            assert_eq!(MRZParser::try_new_mrz_string_parser(input).err(), Some(MrzError::InvalidLineCount(0)));
+            assert!(MRZParser::new_mrz_string_parser(input).parse().is_err());
+        }
+
+        // A single line is read as a driving licence
+        for input in ["P<UTO", "ÖÖÖ"] {
+            assert_eq!(
+                MRZParser::try_new_mrz_string_parser(input).err(),
+                Some(MrzError::InvalidLineLength {
+                    line: 0,
+                    expected: 30,
+                    actual: input.chars().count()
+                })
+            );
+            assert!(MRZParser::new_mrz_string_parser(input).parse().is_err());
+        }
+
+        let mrz_string = "P<UTOERIKSSÖN<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\nL898902C36UTO7408122F1204159ZE184226B<<<<<10";
+        assert_eq!(
+            MRZParser::try_new_mrz_string_parser(mrz_string).err(),
+            Some(MrzError::InvalidCharacter {
+                line: 0,
+                column: 11,
+                character: 'Ö'
+            })
+        );
+
+        // Multi-byte characters are counted as one character, not by their UTF-8 length
+        let short = "P<UTOERIKSSÖN<<ANNA<MARIA<<<<<<<<<<<<<<<<<<\nL898902C36UTO7408122F1204159ZE184226B<<<<<10";
+        assert_eq!(
+            MRZParser::try_new_mrz_string_parser(short).err(),
+            Some(MrzError::InvalidLineLength {
+                line: 0,
+                expected: 44,
+                actual: 43
+            })
+        );
+    }
+
+    #[test]
+    fn test_detect_visas() {
+        let mrva = vec![
+            "V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L8988901C4XXX4009078F96121096ZE184226B<<<<<<".to_string(),
+        ];
+        let mut parser = MRZParser::new_mrz_line_parser(mrva);
+        assert_eq!(parser.get_mrz_type().unwrap(), MRZ_TYPE_MRVA);
+        let result = parser.parse().unwrap();
+        assert_eq!(result.kind, DocumentKind::Visa);
+        assert!(matches!(result.document, MrzDocument::MRVA(_)));
+
+        let mrvb = vec![
+            "V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<".to_string(),
+            "L8988901C4XXX4009078F9612109<<<<<<<<".to_string(),
+        ];
+        let mut parser = MRZParser::new_mrz_line_parser(mrvb);
+        assert_eq!(parser.get_mrz_type().unwrap(), MRZ_TYPE_MRVB);
+        assert!(matches!(parser.parse().unwrap().document, MrzDocument::MRVB(_)));
+    }
+
+    #[test]
+    fn test_detect_french_id_card() {
+        let mut parser = MRZParser::new_mrz_string_parser(
+            "IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013\n0506932020438CHRISTIANE<<NI2906209F3",
+        );
+        assert_eq!(parser.get_mrz_type().unwrap(), MRZ_TYPE_FRENCH_ID);
+        let result = parser.parse().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(
+            result
+                .get(constants::mrz_field_name::SURNAME_FIELD)
+                .unwrap()
+                .value,
+            "DOUEL"
+        );
+    }
+
+    #[test]
+    fn test_detect_driving_licence() {
+        let line = "D1NLD15094962111659VW87XK84MO";
+        let mrz_string = format!("{}{}", line, calculate_check_digits(line).unwrap());
+        let mut parser = MRZParser::new_mrz_string_parser(&mrz_string);
+        assert_eq!(parser.get_mrz_type().unwrap(), MRZ_TYPE_DRIVING_LICENCE);
+        let result = parser.parse().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.kind, DocumentKind::DrivingLicence);
+    }
+
+    // Compares the JSON of a parse result with a snapshot in tests/snapshots, and checks that it deserializes
+    // back to the same result. Set UPDATE_SNAPSHOTS to print the current JSON.
+    #[cfg(feature = "serde")]
+    fn assert_json_snapshot(lines: &[&str], snapshot: &str) {
+        let options = ParserOptions {
+            dates: DateOptions {
+                reference_date: MrzDate::new(2024, 1, 1),
+                ..DateOptions::default()
+            },
+            ..ParserOptions::default()
+        };
+        let lines = lines.iter().map(|line| line.to_string()).collect();
+        let result = MRZParser::new_mrz_line_parser(lines)
+            .with_options(options)
+            .parse()
+            .unwrap();
+
+        let json = serde_json::to_value(&result).unwrap();
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        let expected: serde_json::Value = serde_json::from_str(snapshot).unwrap();
+        assert_eq!(json, expected);
+
+        let deserialized: MRZResult = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_td1() {
+        assert_json_snapshot(
+            &["I<UTOD231458907<<<<<<<<<<<<<<<", "7408122F1204159UTO<<<<<<<<<<<6", "ERIKSSON<<ANNA<MARIA<<<<<<<<<<"],
+            include_str!("../tests/snapshots/td1.json"),
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_td2() {
+        assert_json_snapshot(
+            &["I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<", "D231458907UTO7408122F1204159<<<<<<<6"],
+            include_str!("../tests/snapshots/td2.json"),
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_td3() {
+        assert_json_snapshot(
+            &["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<10"],
+            include_str!("../tests/snapshots/td3.json"),
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_visas() {
+        assert_json_snapshot(
+            &["V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L8988901C4XXX4009078F96121096ZE184226B<<<<<<"],
+            include_str!("../tests/snapshots/mrva.json"),
+        );
+        assert_json_snapshot(
+            &["V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<", "L8988901C4XXX4009078F9612109<<<<<<<<"],
+            include_str!("../tests/snapshots/mrvb.json"),
+        );
+    }
 }
@@ -0,0 +1,70 @@
+use crate::constants::mrz_utils::lookup_code;
+use crate::parser::field_formatter::FieldType;
+use crate::parser::mrz_field::MrzField;
+use crate::parser::strict::field_violations;
+
+// Factor applied to a field whose check digit does not match
+const INVALID_CHECK_DIGIT_FACTOR: f64 = 0.2;
+
+// Factor applied for each character that does not belong in the field, e.g. a digit in a name
+const CHARACTER_VIOLATION_FACTOR: f64 = 0.5;
+
+// Factor applied to a country code that is not a known state or organisation
+const UNKNOWN_CODE_FACTOR: f64 = 0.5;
+
+// Factor applied for each OCR correction made to the field
+const CORRECTION_FACTOR: f64 = 0.8;
+
+// field_confidence: Returns the confidence in a field between 0 and 1, from its check digit, how well its
+// characters fit the field type, whether its country code is known and the OCR corrections made to it.
+pub(crate) fn field_confidence(field_type: FieldType, field: &MrzField) -> f64 {
+    let mut confidence = correction_factor(field.corrections.len());
+
+    if !field.check_digit.is_empty() && !field.is_valid {
+        confidence *= INVALID_CHECK_DIGIT_FACTOR;
+    }
+
+    let chars: Vec<char> = field.raw_value.chars().collect();
+    confidence *= CHARACTER_VIOLATION_FACTOR.powi(field_violations(field_type, &chars).len() as i32);
+
+    if matches!(field_type, FieldType::CountryCode | FieldType::Nationality) && lookup_code(&field.value).is_none() {
+        confidence *= UNKNOWN_CODE_FACTOR;
+    }
+
+    confidence
+}
+
+// correction_factor: Returns the factor applied to the confidence of a field for the given number of
+// OCR corrections.
+pub(crate) fn correction_factor(corrections: usize) -> f64 {
+    CORRECTION_FACTOR.powi(corrections as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::correction::Substitution;
+
+    #[test]
+    fn test_field_confidence() {
+        let field = MrzField::new("UTO".to_string(), "UTO".to_string(), String::new(), true);
+        assert_eq!(field_confidence(FieldType::Nationality, &field), 1.0);
+
+        let field = MrzField::new("XYZ".to_string(), "XYZ".to_string(), String::new(), true);
+        assert_eq!(field_confidence(FieldType::Nationality, &field), 0.5);
+
+        let field = MrzField::new("ERIKSSON ANNA".to_string(), "ERIKSS0N<<ANNA".to_string(), String::new(), true);
+        assert_eq!(field_confidence(FieldType::Names, &field), 0.5);
+
+        let mut field = MrzField::new("740812".to_string(), "740812".to_string(), "3".to_string(), false);
+        assert!((field_confidence(FieldType::Birthdate, &field) - 0.2).abs() < 1e-9);
+
+        field.is_valid = true;
+        field.corrections.push(Substitution {
+            position: 2,
+            from: 'O',
+            to: '0',
+        });
+        assert!((field_confidence(FieldType::Birthdate, &field) - 0.8).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,517 @@
+use crate::constants::mrz_utils::ALPHABET_MAPPING;
+use crate::parser::options::{CorrectionMode, ParserOptions};
+use crate::utils::utils::{calculate_check_digits, is_value_valid, replace_letters};
+
+// Pairs of characters commonly confused by OCR engines. Corrections are tried in both directions. L/1, U/0
+// and G/6 weigh the same in a check digit, so they are only tried where a letter is not allowed.
+const OCR_CONFUSIONS: [(char, char); 12] = [
+    ('O', '0'),
+    ('Q', '0'),
+    ('D', '0'),
+    ('U', '0'),
+    ('I', '1'),
+    ('L', '1'),
+    ('Z', '2'),
+    ('A', '4'),
+    ('S', '5'),
+    ('G', '6'),
+    ('T', '7'),
+    ('B', '8'),
+];
+
+// A single character replaced by a correction. `position` is the offset within the field value,
+// the check digit being at position `value.len()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Substitution {
+    pub position: usize,
+    pub from: char,
+    pub to: char,
+}
+
+// A field value protected by a check digit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedValue {
+    pub value: String,
+    pub check_digit: char,
+    // Whether the value may only hold digits and fillers, as for dates
+    pub numeric: bool,
+}
+
+impl CheckedValue {
+    pub fn new(value: &str, check_digit: char, numeric: bool) -> Self {
+        CheckedValue {
+            value: value.to_string(),
+            check_digit,
+            numeric,
+        }
+    }
+
+    // is_valid: Returns true when the check digit matches the value. An empty (all filler) value may
+    // use a filler as check digit.
+    pub fn is_valid(&self) -> bool {
+        if self.check_digit == '<' {
+            return self.value.trim_matches('<').is_empty();
+        }
+
+        let value_allowed = !self.numeric
+            || self
+                .value
+                .chars()
+                .all(|ch| ch.is_ascii_digit() || ch == '<');
+        value_allowed && is_value_valid(&self.value, &self.check_digit.to_string())
+    }
+}
+
+// The corrected form of a field, with the characters that were replaced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldCorrection {
+    pub value: CheckedValue,
+    pub substitutions: Vec<Substitution>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorrectionOutcome {
+    // All check digits already match, nothing was changed.
+    Valid,
+    // Exactly one reading satisfies every check digit. Holds one entry per input field.
+    Corrected(Vec<FieldCorrection>),
+    // Several readings satisfy every check digit, so none is chosen.
+    Ambiguous(usize),
+    // No reading within the edit budget satisfies every check digit.
+    Uncorrectable,
+}
+
+// Corrects OCR errors in check-digit protected fields by trying the usual character confusions
+// (O/0, I/1, B/8, S/5, Z/2, G/6, ...) and keeping only the readings that satisfy the check digits.
+// Confusions that leave every check digit unchanged are not tried in fields that allow letters, as no
+// check digit could tell the readings apart: the original characters are kept.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckDigitCorrector {
+    max_edits: usize,
+}
+
+impl Default for CheckDigitCorrector {
+    fn default() -> Self {
+        CheckDigitCorrector { max_edits: 2 }
+    }
+}
+
+impl CheckDigitCorrector {
+    // new: Creates a corrector changing at most `max_edits` characters per field.
+    pub fn new(max_edits: usize) -> Self {
+        CheckDigitCorrector { max_edits }
+    }
+
+    // candidates: Returns every reading of the field within the edit budget that satisfies its check digit.
+    // A valid field yields itself with no substitutions.
+    pub fn candidates(&self, field: &CheckedValue) -> Vec<FieldCorrection> {
+        if field.is_valid() {
+            return vec![FieldCorrection {
+                value: field.clone(),
+                substitutions: Vec::new(),
+            }];
+        }
+
+        let mut chars: Vec<char> = field.value.chars().collect();
+        chars.push(field.check_digit);
+
+        let mut candidates = Vec::new();
+        let mut substitutions = Vec::new();
+        self.search(field, &mut chars, 0, &mut substitutions, &mut candidates);
+        candidates
+    }
+
+    // correct: Corrects a group of fields that are also covered by a composite check digit.
+    //
+    // `composite` receives a candidate reading of every field (in input order) and returns whether it
+    // satisfies the composite check digit. Among the readings satisfying every field and composite check
+    // digit, one is only returned when it is the only one: a reading needing more edits than another still
+    // counts, so the outcome is Ambiguous whenever several readings fit.
+    pub fn correct(&self, fields: &[CheckedValue], composite: &dyn Fn(&[CheckedValue]) -> bool) -> CorrectionOutcome {
+        let current: Vec<CheckedValue> = fields.to_vec();
+        if fields.iter().all(CheckedValue::is_valid) && composite(&current) {
+            return CorrectionOutcome::Valid;
+        }
+
+        let candidates: Vec<Vec<FieldCorrection>> = fields.iter().map(|field| self.candidates(field)).collect();
+
+        if candidates.iter().any(Vec::is_empty) {
+            return CorrectionOutcome::Uncorrectable;
+        }
+
+        // First reading satisfying every check digit, and how many do
+        let mut found: Option<Vec<FieldCorrection>> = None;
+        let mut count = 0;
+        let mut selection: Vec<usize> = vec![0; fields.len()];
+
+        // Walk the cartesian product of the candidates of every field
+        loop {
+            let reading: Vec<FieldCorrection> = selection
+                .iter()
+                .enumerate()
+                .map(|(idx, &pick)| candidates[idx][pick].clone())
+                .collect();
+            let values: Vec<CheckedValue> = reading
+                .iter()
+                .map(|correction| correction.value.clone())
+                .collect();
+
+            if composite(&values) {
+                count += 1;
+                found.get_or_insert(reading);
+            }
+
+            let mut idx = 0;
+            while idx < selection.len() {
+                selection[idx] += 1;
+                if selection[idx] < candidates[idx].len() {
+                    break;
+                }
+                selection[idx] = 0;
+                idx += 1;
+            }
+            if idx == selection.len() {
+                break;
+            }
+        }
+
+        match found {
+            None => CorrectionOutcome::Uncorrectable,
+            Some(reading) if count == 1 => CorrectionOutcome::Corrected(reading),
+            Some(_) => CorrectionOutcome::Ambiguous(count),
+        }
+    }
+
+    fn search(
+        &self, field: &CheckedValue, chars: &mut [char], start: usize, substitutions: &mut Vec<Substitution>,
+        candidates: &mut Vec<FieldCorrection>,
+    ) {
+        if !substitutions.is_empty() {
+            let (value, check_digit) = chars.split_at(chars.len() - 1);
+            let candidate = CheckedValue {
+                value: value.iter().collect(),
+                check_digit: check_digit[0],
+                numeric: field.numeric,
+            };
+            if candidate.is_valid() {
+                candidates.push(FieldCorrection {
+                    value: candidate,
+                    substitutions: substitutions.clone(),
+                });
+            }
+        }
+
+        if substitutions.len() == self.max_edits {
+            return;
+        }
+
+        for position in start..chars.len() {
+            let original = chars[position];
+            let digits_only = field.numeric || position == chars.len() - 1;
+
+            let allowed = |ch: &char| {
+                if digits_only {
+                    ch.is_ascii_digit()
+                } else {
+                    changes_check_digits(original, *ch)
+                }
+            };
+
+            for replacement in confusions(original).filter(allowed) {
+                chars[position] = replacement;
+                substitutions.push(Substitution {
+                    position,
+                    from: original,
+                    to: replacement,
+                });
+                self.search(field, chars, position + 1, substitutions, candidates);
+                substitutions.pop();
+            }
+            chars[position] = original;
+        }
+    }
+}
+
+// Location of a check-digit protected field in the MRZ lines, the check digit following the value
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CheckedSpan {
+    pub line: usize,
+    pub start: usize,
+    pub length: usize,
+    pub numeric: bool,
+    // Number of characters continuing the value after a filler in place of its check digit, as for long TD1
+    // document numbers. The check digit then follows these characters.
+    pub overflow: usize,
+}
+
+impl CheckedSpan {
+    // columns: Returns the columns holding the value, skipping the filler before any overflow.
+    pub fn columns(&self) -> impl Iterator<Item = usize> {
+        let end = self.start + self.length;
+        let overflow_start = if self.overflow == 0 { end } else { end + 1 };
+        (self.start..end).chain(overflow_start..overflow_start + self.overflow)
+    }
+
+    // check_column: Returns the column of the check digit.
+    pub fn check_column(&self) -> usize {
+        match self.overflow {
+            0 => self.start + self.length,
+            overflow => self.start + self.length + 1 + overflow,
+        }
+    }
+}
+
+// Location of the composite check digit and of the (line, start, end) segments it covers
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CompositeSpan {
+    pub segments: &'static [(usize, usize, usize)],
+    pub line: usize,
+    pub position: usize,
+}
+
+// correct_lines: Runs the corrector over the given fields of the MRZ lines, rewriting the lines when
+// a single correction is found. Returns the substitutions applied to each span.
+pub(crate) fn correct_lines(
+    corrector: &CheckDigitCorrector, lines: &mut [String], spans: &[CheckedSpan], composite: Option<&CompositeSpan>,
+) -> Vec<Vec<Substitution>> {
+    let mut applied = vec![Vec::new(); spans.len()];
+    if !lines.iter().all(|line| line.is_ascii()) {
+        return applied;
+    }
+
+    let fields: Vec<CheckedValue> = spans
+        .iter()
+        .map(|span| {
+            let line = lines[span.line].as_bytes();
+            let value: String = span.columns().map(|column| line[column] as char).collect();
+            let check_digit = line
+                .get(span.check_column())
+                .map_or('<', |&byte| byte as char);
+            CheckedValue::new(&value, check_digit, span.numeric)
+        })
+        .collect();
+
+    let write = |lines: &mut [String], values: &[CheckedValue]| {
+        for (span, value) in spans.iter().zip(values) {
+            let columns = span.columns().chain([span.check_column()]);
+            for (column, ch) in columns.zip(value.value.chars().chain([value.check_digit])) {
+                lines[span.line].replace_range(column..=column, ch.encode_utf8(&mut [0; 4]));
+            }
+        }
+    };
+
+    let template: Vec<String> = lines.to_vec();
+    let matches_composite = |values: &[CheckedValue]| match composite {
+        None => true,
+        Some(composite) => {
+            let mut candidate = template.clone();
+            write(&mut candidate, values);
+            let covered: String = composite
+                .segments
+                .iter()
+                .map(|&(line, start, end)| &candidate[line][start..end])
+                .collect();
+            let check_digit = replace_letters(&candidate[composite.line][composite.position..=composite.position]);
+            calculate_check_digits(&covered).is_ok_and(|digit| digit == check_digit)
+        }
+    };
+
+    if let CorrectionOutcome::Corrected(corrections) = corrector.correct(&fields, &matches_composite) {
+        let values: Vec<CheckedValue> = corrections
+            .iter()
+            .map(|correction| correction.value.clone())
+            .collect();
+        write(lines, &values);
+        for (idx, correction) in corrections.into_iter().enumerate() {
+            applied[idx] = correction.substitutions;
+        }
+    }
+
+    applied
+}
+
+// guided_corrections: Runs the check-digit guided correction over the lines when the options enable it.
+// Returns the substitutions applied to each span, none when the correction is disabled.
+pub(crate) fn guided_corrections(
+    options: &ParserOptions, lines: &mut [String], spans: &[CheckedSpan], composite: Option<&CompositeSpan>,
+) -> Vec<Vec<Substitution>> {
+    if options.correction != CorrectionMode::CheckDigitGuided {
+        return vec![Vec::new(); spans.len()];
+    }
+
+    correct_lines(&CheckDigitCorrector::default(), lines, spans, composite)
+}
+
+// diff_substitutions: Lists the characters that differ between a value and its corrected form,
+// with positions shifted by `offset`.
+pub(crate) fn diff_substitutions(original: &str, corrected: &str, offset: usize) -> Vec<Substitution> {
+    original
+        .chars()
+        .zip(corrected.chars())
+        .enumerate()
+        .filter(|(_, (from, to))| from != to)
+        .map(|(idx, (from, to))| Substitution {
+            position: offset + idx,
+            from,
+            to,
+        })
+        .collect()
+}
+
+// Characters an OCR engine may have confused with the given one
+fn confusions(ch: char) -> impl Iterator<Item = char> {
+    OCR_CONFUSIONS.iter().filter_map(move |&(letter, digit)| {
+        if letter == ch {
+            Some(digit)
+        } else if digit == ch {
+            Some(letter)
+        } else {
+            None
+        }
+    })
+}
+
+// Whether replacing a character changes the check digits covering it. Characters whose values differ by a
+// multiple of 10 weigh the same with every weight.
+fn changes_check_digits(from: char, to: char) -> bool {
+    (check_value(from) - check_value(to)) % 10 != 0
+}
+
+fn check_value(ch: char) -> i32 {
+    match ch.to_digit(10) {
+        Some(digit) => digit as i32,
+        None => ALPHABET_MAPPING.get(&ch).copied().unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Composite check of a TD3 second line: document number, birth date, expiry date and personal number
+    fn td3_composite(expected: &'static str) -> impl Fn(&[CheckedValue]) -> bool {
+        move |fields: &[CheckedValue]| {
+            let composite: String = fields
+                .iter()
+                .map(|field| format!("{}{}", field.value, field.check_digit))
+                .collect();
+            calculate_check_digits(&composite).is_ok_and(|digit| digit == expected)
+        }
+    }
+
+    fn td3_fields(document_number: &str, birth_date: &str) -> Vec<CheckedValue> {
+        vec![
+            CheckedValue::new(document_number, '6', false),
+            CheckedValue::new(birth_date, '2', true),
+            CheckedValue::new("120415", '9', true),
+            CheckedValue::new("ZE184226B<<<<<", '1', false),
+        ]
+    }
+
+    #[test]
+    fn test_valid_fields() {
+        let corrector = CheckDigitCorrector::default();
+        let outcome = corrector.correct(&td3_fields("L898902C3", "740812"), &td3_composite("0"));
+        assert_eq!(outcome, CorrectionOutcome::Valid);
+    }
+
+    #[test]
+    fn test_correct_document_number() {
+        let corrector = CheckDigitCorrector::default();
+        let mut fields = td3_fields("L898902C3", "740812");
+        fields[0].check_digit = 'G';
+        let outcome = corrector.correct(&fields, &td3_composite("0"));
+
+        match outcome {
+            CorrectionOutcome::Corrected(fields) => {
+                assert_eq!(fields[0].value.check_digit, '6');
+                assert_eq!(
+                    fields[0].substitutions,
+                    vec![Substitution {
+                        position: 9,
+                        from: 'G',
+                        to: '6'
+                    }]
+                );
+                assert!(fields[1].substitutions.is_empty());
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_correct_date() {
+        let corrector = CheckDigitCorrector::default();
+        let outcome = corrector.correct(&td3_fields("L898902C3", "74O8I2"), &td3_composite("0"));
+
+        match outcome {
+            CorrectionOutcome::Corrected(fields) => {
+                assert_eq!(fields[1].value.value, "740812");
+                assert_eq!(fields[1].substitutions.len(), 2);
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        // Several single-edit readings of the document number satisfy both check digits
+        let corrector = CheckDigitCorrector::new(1);
+        let outcome = corrector.correct(&td3_fields("LB98902C3", "740812"), &td3_composite("0"));
+        assert!(matches!(outcome, CorrectionOutcome::Ambiguous(_)));
+    }
+
+    #[test]
+    fn test_ambiguous_edit_counts() {
+        // `LB9B902C3` with check digit 9 reads as `LB9B9D2C3` with one edit, and as `LB989O2C3` with two
+        // (B/8 and 0/O). Both readings count, even though one needs fewer edits.
+        let corrector = CheckDigitCorrector::new(2);
+        let fields = vec![CheckedValue::new("LB9B902C3", '9', false)];
+        let candidates = corrector.candidates(&fields[0]);
+        assert!(candidates
+            .iter()
+            .any(|candidate| candidate.substitutions.len() == 1));
+        assert!(candidates
+            .iter()
+            .any(|candidate| candidate.substitutions.len() == 2));
+        assert_eq!(corrector.correct(&fields, &|_| true), CorrectionOutcome::Ambiguous(candidates.len()));
+    }
+
+    #[test]
+    fn test_undetectable_confusions() {
+        // L/1, U/0 and G/6 keep every check digit, so they are not tried where letters are allowed
+        let corrector = CheckDigitCorrector::default();
+        let candidates = corrector.candidates(&CheckedValue::new("L8U8G02C3", '8', false));
+        assert!(candidates.iter().all(|candidate| {
+            candidate
+                .substitutions
+                .iter()
+                .all(|substitution| changes_check_digits(substitution.from, substitution.to))
+        }));
+
+        // In a date they are still read as digits
+        let candidates = corrector.candidates(&CheckedValue::new("74O8L2", '2', true));
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].value.value, "740812");
+    }
+
+    #[test]
+    fn test_field_candidates() {
+        let corrector = CheckDigitCorrector::new(1);
+        let candidates = corrector.candidates(&CheckedValue::new("L898902C3", '8', false));
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.value.is_valid()));
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.substitutions.len() == 1));
+    }
+
+    #[test]
+    fn test_uncorrectable() {
+        let corrector = CheckDigitCorrector::new(1);
+        let outcome = corrector.correct(&td3_fields("L898902C3", "740812"), &td3_composite("5"));
+        assert_eq!(outcome, CorrectionOutcome::Uncorrectable);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::constants::mrz_field_name::{
+    BIRTHDATE_FIELD, COUNTRY_CODE_FIELD, DOCUMENT_NUMBER_FIELD, DOCUMENT_TYPE_FIELD, EXPIRY_DATE_FIELD,
+    FINAL_CHECK_DIGIT_FIELD, GIVEN_NAMES_FIELD, ISSUE_DATE_FIELD, ISSUING_DEPARTMENT_FIELD, ISSUING_OFFICE_FIELD,
+    NAME_FIELD, NATIONALITY_FIELD, OPTIONAL_DATA_1_FIELD, OPTIONAL_DATA_2_FIELD, SEX_FIELD, SURNAME_FIELD,
+    VERSION_FIELD,
+};
+use crate::parser::mrz_field::MrzField;
+use std::collections::HashMap;
+
+// Fields of a TD1 (3 lines of 30 characters) document
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TD1Document {
+    pub document_type: MrzField,
+    pub issuing_country: MrzField,
+    pub document_number: MrzField,
+    pub optional_data_1: MrzField,
+    pub birth_date: MrzField,
+    pub sex: MrzField,
+    pub expiry_date: MrzField,
+    pub nationality: MrzField,
+    pub optional_data_2: MrzField,
+    pub composite_check_digit: MrzField,
+    pub names: MrzField,
+}
+
+// Fields of a TD2 (2 lines of 36 characters) document
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TD2Document {
+    pub document_type: MrzField,
+    pub issuing_country: MrzField,
+    pub names: MrzField,
+    pub document_number: MrzField,
+    pub nationality: MrzField,
+    pub birth_date: MrzField,
+    pub sex: MrzField,
+    pub expiry_date: MrzField,
+    pub optional_data_1: MrzField,
+    pub composite_check_digit: MrzField,
+}
+
+// Fields of a TD3 (2 lines of 44 characters) document
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TD3Document {
+    pub document_type: MrzField,
+    pub issuing_country: MrzField,
+    pub names: MrzField,
+    pub document_number: MrzField,
+    pub nationality: MrzField,
+    pub birth_date: MrzField,
+    pub sex: MrzField,
+    pub expiry_date: MrzField,
+    pub optional_data_1: MrzField,
+    pub composite_check_digit: MrzField,
+}
+
+// Fields of a machine readable visa, MRV-A (2 lines of 44 characters) or MRV-B (2 lines of 36 characters).
+// Visas carry no composite check digit, and the optional data fills the rest of the second line.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRVDocument {
+    pub document_type: MrzField,
+    pub issuing_country: MrzField,
+    pub names: MrzField,
+    pub document_number: MrzField,
+    pub nationality: MrzField,
+    pub birth_date: MrzField,
+    pub sex: MrzField,
+    pub expiry_date: MrzField,
+    pub optional_data: MrzField,
+}
+
+// Fields of a French national identity card issued before 2021 (2 lines of 36 characters). The card number
+// starts with the issue year and month and the issuing department, and the composite check digit covers
+// both lines.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrenchIdCardDocument {
+    pub document_type: MrzField,
+    pub issuing_country: MrzField,
+    pub surname: MrzField,
+    pub issuing_department: MrzField,
+    pub issuing_office: MrzField,
+    pub issue_date: MrzField,
+    pub document_number: MrzField,
+    pub given_names: MrzField,
+    pub birth_date: MrzField,
+    pub sex: MrzField,
+    pub composite_check_digit: MrzField,
+}
+
+// Fields of a single-line driving licence MRZ (1 line of 30 characters). The check digit covers the whole line.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrivingLicenceDocument {
+    pub document_type: MrzField,
+    pub issuing_country: MrzField,
+    pub version: MrzField,
+    pub document_number: MrzField,
+    pub optional_data: MrzField,
+    pub check_digit: MrzField,
+}
+
+// The kind of document, read from the first letter of its document type code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DocumentKind {
+    // Passports and other travel documents in TD3 size (`P`).
+    Passport,
+    // Official travel documents such as identity cards (`I`, `A` or `C`).
+    IdCard,
+    // Machine readable visas (`V`).
+    Visa,
+    // Driving licences, recognised by their format rather than by their document type code.
+    DrivingLicence,
+    // Any other document type code.
+    Other,
+}
+
+impl DocumentKind {
+    // from_document_type: Classifies a document from its document type code.
+    pub fn from_document_type(document_type: &str) -> Self {
+        match document_type.chars().next() {
+            Some('P') => DocumentKind::Passport,
+            Some('I' | 'A' | 'C') => DocumentKind::IdCard,
+            Some('V') => DocumentKind::Visa,
+            _ => DocumentKind::Other,
+        }
+    }
+}
+
+// The typed fields of a parsed MRZ, one variant per format.
+// Custom holds the fields produced by a user-provided IMRZParser implementation.
+// With the `serde` feature, a document is written as {"format": "td3", "fields": {...}}, the format being one of
+// td1, td2, td3, mrva, mrvb, french_id_card, driving_licence or custom.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "format", content = "fields", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum MrzDocument {
+    #[cfg_attr(feature = "serde", serde(rename = "td1"))]
+    TD1(TD1Document),
+    #[cfg_attr(feature = "serde", serde(rename = "td2"))]
+    TD2(TD2Document),
+    #[cfg_attr(feature = "serde", serde(rename = "td3"))]
+    TD3(TD3Document),
+    #[cfg_attr(feature = "serde", serde(rename = "mrva"))]
+    MRVA(MRVDocument),
+    #[cfg_attr(feature = "serde", serde(rename = "mrvb"))]
+    MRVB(MRVDocument),
+    FrenchIdCard(FrenchIdCardDocument),
+    DrivingLicence(DrivingLicenceDocument),
+    Custom(HashMap<String, MrzField>),
+}
+
+impl MrzDocument {
+    // fields: Returns a map view of the fields keyed by the names in constants::mrz_field_name.
+    // Fields absent from the format (e.g. the composite check digit of a visa) are not present.
+    pub fn fields(&self) -> HashMap<&str, &MrzField> {
+        let mut fields: HashMap<&str, &MrzField> = HashMap::new();
+
+        match self {
+            MrzDocument::TD1(doc) => {
+                fields.insert(DOCUMENT_TYPE_FIELD, &doc.document_type);
+                fields.insert(COUNTRY_CODE_FIELD, &doc.issuing_country);
+                fields.insert(DOCUMENT_NUMBER_FIELD, &doc.document_number);
+                fields.insert(OPTIONAL_DATA_1_FIELD, &doc.optional_data_1);
+                fields.insert(BIRTHDATE_FIELD, &doc.birth_date);
+                fields.insert(SEX_FIELD, &doc.sex);
+                fields.insert(EXPIRY_DATE_FIELD, &doc.expiry_date);
+                fields.insert(NATIONALITY_FIELD, &doc.nationality);
+                fields.insert(OPTIONAL_DATA_2_FIELD, &doc.optional_data_2);
+                fields.insert(FINAL_CHECK_DIGIT_FIELD, &doc.composite_check_digit);
+                fields.insert(NAME_FIELD, &doc.names);
+            }
+            MrzDocument::TD2(doc) => {
+                fields.insert(DOCUMENT_TYPE_FIELD, &doc.document_type);
+                fields.insert(COUNTRY_CODE_FIELD, &doc.issuing_country);
+                fields.insert(NAME_FIELD, &doc.names);
+                fields.insert(DOCUMENT_NUMBER_FIELD, &doc.document_number);
+                fields.insert(NATIONALITY_FIELD, &doc.nationality);
+                fields.insert(BIRTHDATE_FIELD, &doc.birth_date);
+                fields.insert(SEX_FIELD, &doc.sex);
+                fields.insert(EXPIRY_DATE_FIELD, &doc.expiry_date);
+                fields.insert(OPTIONAL_DATA_1_FIELD, &doc.optional_data_1);
+                fields.insert(FINAL_CHECK_DIGIT_FIELD, &doc.composite_check_digit);
+            }
+            MrzDocument::TD3(doc) => {
+                fields.insert(DOCUMENT_TYPE_FIELD, &doc.document_type);
+                fields.insert(COUNTRY_CODE_FIELD, &doc.issuing_country);
+                fields.insert(NAME_FIELD, &doc.names);
+                fields.insert(DOCUMENT_NUMBER_FIELD, &doc.document_number);
+                fields.insert(NATIONALITY_FIELD, &doc.nationality);
+                fields.insert(BIRTHDATE_FIELD, &doc.birth_date);
+                fields.insert(SEX_FIELD, &doc.sex);
+                fields.insert(EXPIRY_DATE_FIELD, &doc.expiry_date);
+                fields.insert(OPTIONAL_DATA_1_FIELD, &doc.optional_data_1);
+                fields.insert(FINAL_CHECK_DIGIT_FIELD, &doc.composite_check_digit);
+            }
+            MrzDocument::MRVA(doc) | MrzDocument::MRVB(doc) => {
+                fields.insert(DOCUMENT_TYPE_FIELD, &doc.document_type);
+                fields.insert(COUNTRY_CODE_FIELD, &doc.issuing_country);
+                fields.insert(NAME_FIELD, &doc.names);
+                fields.insert(DOCUMENT_NUMBER_FIELD, &doc.document_number);
+                fields.insert(NATIONALITY_FIELD, &doc.nationality);
+                fields.insert(BIRTHDATE_FIELD, &doc.birth_date);
+                fields.insert(SEX_FIELD, &doc.sex);
+                fields.insert(EXPIRY_DATE_FIELD, &doc.expiry_date);
+                fields.insert(OPTIONAL_DATA_1_FIELD, &doc.optional_data);
+            }
+            MrzDocument::FrenchIdCard(doc) => {
+                fields.insert(DOCUMENT_TYPE_FIELD, &doc.document_type);
+                fields.insert(COUNTRY_CODE_FIELD, &doc.issuing_country);
+                fields.insert(SURNAME_FIELD, &doc.surname);
+                fields.insert(ISSUING_DEPARTMENT_FIELD, &doc.issuing_department);
+                fields.insert(ISSUING_OFFICE_FIELD, &doc.issuing_office);
+                fields.insert(ISSUE_DATE_FIELD, &doc.issue_date);
+                fields.insert(DOCUMENT_NUMBER_FIELD, &doc.document_number);
+                fields.insert(GIVEN_NAMES_FIELD, &doc.given_names);
+                fields.insert(BIRTHDATE_FIELD, &doc.birth_date);
+                fields.insert(SEX_FIELD, &doc.sex);
+                fields.insert(FINAL_CHECK_DIGIT_FIELD, &doc.composite_check_digit);
+            }
+            MrzDocument::DrivingLicence(doc) => {
+                fields.insert(DOCUMENT_TYPE_FIELD, &doc.document_type);
+                fields.insert(COUNTRY_CODE_FIELD, &doc.issuing_country);
+                fields.insert(VERSION_FIELD, &doc.version);
+                fields.insert(DOCUMENT_NUMBER_FIELD, &doc.document_number);
+                fields.insert(OPTIONAL_DATA_1_FIELD, &doc.optional_data);
+                fields.insert(FINAL_CHECK_DIGIT_FIELD, &doc.check_digit);
+            }
+            MrzDocument::Custom(custom) => {
+                for (name, field) in custom {
+                    fields.insert(name.as_str(), field);
+                }
+            }
+        }
+
+        fields
+    }
+
+    // get: Returns the field with the given name, or None if the format does not have it.
+    pub fn get(&self, name: &str) -> Option<&MrzField> {
+        self.fields().get(name).copied()
+    }
+}
@@ -0,0 +1,203 @@
+use crate::constants::mrz_utils::DRIVING_LICENCE_NUMBER_OF_CHARACTERS_PER_LINE;
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{guided_corrections, CheckedSpan, Substitution};
+use crate::parser::document::{DocumentKind, DrivingLicenceDocument, MrzDocument};
+use crate::parser::field_formatter::FieldFormatter;
+use crate::parser::field_formatter::FieldType::{CountryCode, DocumentNumber, Hash, OptionalData};
+use crate::parser::options::ParserOptions;
+use crate::parser::parser::{IMRZParser, MRZResult};
+use crate::parser::repair::{repair_lines, LineTemplate};
+use crate::parser::strict::{check_strict, FieldSpec};
+use crate::utils::utils::is_value_valid;
+
+// The check digit at the end of the line covers all the characters before it
+const CHECKED_SPANS: [CheckedSpan; 1] = [CheckedSpan {
+    line: 0,
+    start: 0,
+    length: 29,
+    numeric: false,
+    overflow: 0,
+}];
+
+// First column of each field covered by the check digit, in line order: document type, country code,
+// version, document number, optional data and the check digit itself
+const FIELD_STARTS: [usize; 6] = [0, 2, 5, 6, 16, 29];
+
+// Fields checked in strict mode. The document type may hold a digit (e.g. `D1`), so it is checked as data.
+const FIELD_SPECS: [FieldSpec; 5] = [
+    FieldSpec::new(OptionalData, 0, 0, 2, false),
+    FieldSpec::new(CountryCode, 0, 2, 3, false),
+    FieldSpec::new(OptionalData, 0, 5, 1, false),
+    FieldSpec::new(DocumentNumber, 0, 6, 10, false),
+    FieldSpec::new(Hash, 0, 29, 1, false),
+];
+
+// Layout of the checked fields, used to repair line lengths and to decode OCR alternatives
+pub(crate) const TEMPLATE: LineTemplate = LineTemplate {
+    specs: &FIELD_SPECS,
+    spans: &CHECKED_SPANS,
+    composite: None,
+};
+
+// Parser for single-line driving licence MRZs (1 line of 30 characters), such as the ISO 18013 `D1`
+// licences and the Swiss `FA` licences
+#[derive(Default, Debug, Clone)]
+pub struct DrivingLicence {
+    options: ParserOptions,
+}
+
+impl DrivingLicence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        DrivingLicence { options }
+    }
+}
+
+impl IMRZParser for DrivingLicence {
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        let (mut lines, repairs) =
+            repair_lines(&self.options, input, 1, DRIVING_LICENCE_NUMBER_OF_CHARACTERS_PER_LINE, &TEMPLATE)?;
+
+        if self.options.strict {
+            check_strict(&lines, &FIELD_SPECS)?;
+        }
+
+        let guided = guided_corrections(&self.options, &mut lines, &CHECKED_SPANS, None);
+
+        let line = &lines[0];
+        let formatter = FieldFormatter::new(self.options.correction, self.options.dates);
+
+        // The document type and version are read as data, as letter/digit correction does not apply to them
+        let mut document_type = formatter.field(OptionalData, line, 0, 2, false)?;
+
+        let mut country_code = formatter.field(CountryCode, line, 2, 3, false)?;
+
+        let mut version = formatter.field(OptionalData, line, 5, 1, false)?;
+
+        let mut document_number = formatter.field(DocumentNumber, line, 6, 10, false)?;
+
+        let mut optional_data = formatter.field(OptionalData, line, 16, 13, false)?;
+
+        let mut check_digit = formatter.field(Hash, line, 29, 1, false)?;
+
+        let mut owned = split_corrections(&guided[0]);
+        let fields = [
+            &mut document_type, &mut country_code, &mut version, &mut document_number, &mut optional_data,
+            &mut check_digit,
+        ];
+        for (field, corrections) in fields.into_iter().zip(owned.iter_mut()) {
+            field.append_corrections(corrections);
+        }
+
+        let covered = format!(
+            "{}{}{}{}{}",
+            document_type.raw_value,
+            country_code.raw_value,
+            version.raw_value,
+            document_number.raw_value,
+            optional_data.raw_value
+        );
+        let is_valid = is_value_valid(&covered, &check_digit.raw_value);
+
+        let document = MrzDocument::DrivingLicence(DrivingLicenceDocument {
+            document_type,
+            issuing_country: country_code,
+            version,
+            document_number,
+            optional_data,
+            check_digit,
+        });
+
+        MRZResult::new(&self.options, document, DocumentKind::DrivingLicence, is_valid, repairs)
+    }
+}
+
+// Splits the substitutions of the line-wide span by the field owning their column, with positions relative
+// to the start of that field
+fn split_corrections(substitutions: &[Substitution]) -> Vec<Vec<Substitution>> {
+    let mut owned = vec![Vec::new(); FIELD_STARTS.len()];
+
+    for substitution in substitutions {
+        let idx = FIELD_STARTS
+            .iter()
+            .rposition(|&start| start <= substitution.position)
+            .unwrap_or(0);
+        owned[idx].push(Substitution {
+            position: substitution.position - FIELD_STARTS[idx],
+            ..*substitution
+        });
+    }
+
+    owned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::mrz_field_name::{DOCUMENT_NUMBER_FIELD, OPTIONAL_DATA_1_FIELD, VERSION_FIELD};
+    use crate::parser::options::CorrectionMode;
+    use crate::utils::utils::calculate_check_digits;
+
+    fn licence(without_check_digit: &str) -> Vec<String> {
+        let check_digit = calculate_check_digits(without_check_digit).unwrap();
+        vec![format!("{}{}", without_check_digit, check_digit)]
+    }
+
+    #[test]
+    fn test_parse_driving_licence() {
+        let result = DrivingLicence::new()
+            .parse(&licence("D1NLD15094962111659VW87XK84MO"))
+            .unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.kind, DocumentKind::DrivingLicence);
+        assert_eq!(result.issuing_state, "Netherlands");
+        assert_eq!(result.get(DOCUMENT_NUMBER_FIELD).unwrap().value, "5094962111");
+        assert_eq!(result.get(VERSION_FIELD).unwrap().value, "1");
+
+        let result = DrivingLicence::new()
+            .parse(&licence("FACHE1AAA001D2<<<<<<<<<<<<<<<"))
+            .unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.issuing_state, "Switzerland");
+        assert_eq!(result.get(DOCUMENT_NUMBER_FIELD).unwrap().value, "AAA001D2");
+    }
+
+    #[test]
+    fn test_parse_driving_licence_guided_correction() {
+        // The `8` of the optional data, at column 19, was read as `B`
+        let mut lines = licence("FACHE1KMNPRWXY<<VWX8K<<<<<<<<");
+        lines[0].replace_range(19..20, "B");
+        let parser = DrivingLicence::with_options(ParserOptions {
+            correction: CorrectionMode::CheckDigitGuided,
+            ..ParserOptions::default()
+        });
+
+        let result = parser.parse(&lines).unwrap();
+        assert!(result.is_valid);
+        assert!(result
+            .get(DOCUMENT_NUMBER_FIELD)
+            .unwrap()
+            .corrections
+            .is_empty());
+        assert_eq!(result.get(DOCUMENT_NUMBER_FIELD).unwrap().value, "KMNPRWXY");
+        assert_eq!(
+            result.get(OPTIONAL_DATA_1_FIELD).unwrap().corrections,
+            vec![Substitution {
+                position: 3,
+                from: 'B',
+                to: '8'
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_driving_licence_invalid_check_digit() {
+        let mut lines = licence("D1NLD15094962111659VW87XK84MO");
+        lines[0].replace_range(10..11, "8");
+        let result = DrivingLicence::new().parse(&lines).unwrap();
+        assert!(!result.is_valid);
+    }
+}
@@ -1,4 +1,11 @@
-use crate::parser::mrz_field::MrzField;
+use crate::error::mrz_error::MrzError;
+use crate::parser::confidence::field_confidence;
+use crate::parser::correction::diff_substitutions;
+use crate::parser::mrz_date::{DateKind, DateOptions, MrzDate};
+use crate::parser::mrz_field::{MrzField, ParsedValue};
+use crate::parser::names::Names;
+use crate::parser::options::CorrectionMode;
+use crate::parser::sex::Sex;
 use crate::utils::utils::{replace_digits, replace_letters};
 
 #[derive(Debug, Clone, Copy)]
@@ -6,6 +13,7 @@ pub enum FieldType {
     Names,
     Birthdate,
     ExpiryDate,
+    IssueDate,
     Sex,
     PersonalNumber,
     OptionalData,
@@ -13,26 +21,27 @@ pub enum FieldType {
     DocumentNumber,
     CountryCode,
     Nationality,
-    Alphabetic,
-    Numeric,
     Hash,
 }
 
 #[derive(Debug)]
 pub struct FieldFormatter {
-    ocr_correction: bool,
+    correction: CorrectionMode,
+    dates: DateOptions,
 }
 
 impl FieldFormatter {
-    pub fn new(ocr_correction: bool) -> Self {
-        FieldFormatter { ocr_correction }
+    pub fn new(correction: CorrectionMode, dates: DateOptions) -> Self {
+        FieldFormatter { correction, dates }
     }
 
     pub fn field(
         &self, field_type: FieldType, from: &str, start_idx: usize, length: usize, check_digit_follow: bool,
-    ) -> Result<MrzField, &'static str> {
+    ) -> Result<MrzField, MrzError> {
         let end_idx = start_idx + length;
-        let raw_value = &from[start_idx..end_idx];
+        let raw_value = from
+            .get(start_idx..end_idx)
+            .ok_or_else(|| out_of_range(from, end_idx))?;
         let mut check_digit = String::new();
 
         if check_digit_follow {
@@ -43,12 +52,22 @@ impl FieldFormatter {
                 .unwrap_or_default();
         }
 
-        let mut corrected_raw_value = raw_value.to_string();
-        if self.ocr_correction {
-            corrected_raw_value = self.correct(&raw_value, field_type);
+        let corrected_raw_value = self.correct(raw_value, field_type);
+        let mut corrections = diff_substitutions(raw_value, &corrected_raw_value, 0);
+
+        if check_digit_follow && self.correction != CorrectionMode::Off {
+            let corrected_check_digit = self.replace_letters(&check_digit);
+            corrections.extend(diff_substitutions(&check_digit, &corrected_check_digit, length));
+            check_digit = corrected_check_digit;
+        }
+
+        // A field left empty may use a filler as check digit, which reads as 0
+        if check_digit == "<" && corrected_raw_value.trim_matches('<').is_empty() {
+            check_digit = "0".to_string();
         }
 
         let formatted_value = self.format(&corrected_raw_value, field_type)?;
+        let parsed = self.parse(&corrected_raw_value, field_type)?;
         let is_valid = true;
 
         let mut result = MrzField {
@@ -56,56 +75,36 @@ impl FieldFormatter {
             raw_value: corrected_raw_value,
             check_digit,
             is_valid,
+            parsed,
+            corrections,
+            confidence: 1.0,
         };
 
         if check_digit_follow {
-            result.is_valid();
+            result.is_valid = result.is_valid();
         }
+        result.confidence = field_confidence(field_type, &result);
 
         Ok(result)
     }
 
-    fn sex(&self, from: &str) -> &str {
-        match from {
-            "M" => "MALE",
-            "F" => "FEMALE",
-            "<" => "UNSPECIFIED",
-            _ => "",
-        }
-    }
-
-    fn names(&self, from: &str) -> Vec<String> {
-        let identifiers: Vec<&str> = from.split("<<").collect();
-        let primary = identifiers[0].replace('<', " ");
-        let secondary = if identifiers.len() > 1 {
-            identifiers[1].replace('<', " ")
-        } else {
-            String::new()
-        };
-        vec![primary, secondary]
-    }
-
-    fn text(&self, from: &str) -> String {
-        from.replace('<', " ")
-    }
-
-    fn date(&self, from: &str) -> Result<String, &'static str> {
-        if from.contains('<') {
-            return Ok(from.to_string());
-        }
-
-        for digit in from.chars() {
-            if digit != '<' && !digit.is_digit(10) {
-                return Err("Invalid birthdate character");
+    // Typed parsing logic
+    fn parse(&self, from: &str, field_type: FieldType) -> Result<Option<ParsedValue>, MrzError> {
+        let kind = match field_type {
+            FieldType::Birthdate => DateKind::Birth,
+            FieldType::ExpiryDate => DateKind::Expiry,
+            // Issue dates are written as YYMM, without a day
+            FieldType::IssueDate => {
+                return MrzDate::parse(&format!("{}<<", from), DateKind::Issue, &self.dates)
+                    .map(|date| Some(ParsedValue::Date(date)))
+                    .map_err(|_| MrzError::InvalidDate(from.to_string()));
             }
-        }
+            FieldType::Names => return Ok(Some(ParsedValue::Names(Names::from_mrz(from)))),
+            FieldType::Sex => return Sex::parse(from).map(|sex| Some(ParsedValue::Sex(sex))),
+            _ => return Ok(None),
+        };
 
-        // Simulate date parsing logic (adjust as necessary for your use case)
-        if from.len() == 6 {
-            Ok(from.to_string())
-        } else {
-            Err("Invalid date format")
-        }
+        MrzDate::parse(from, kind, &self.dates).map(|date| Some(ParsedValue::Date(date)))
     }
 
     // Replace methods
@@ -119,27 +118,41 @@ impl FieldFormatter {
 
     // Correction logic
     pub fn correct(&self, from: &str, field_type: FieldType) -> String {
-        match field_type {
-            FieldType::Birthdate | FieldType::ExpiryDate | FieldType::Hash | FieldType::Numeric => {
+        match (self.correction, field_type) {
+            (CorrectionMode::Off, _) => from.to_string(),
+            (_, FieldType::Birthdate | FieldType::ExpiryDate | FieldType::IssueDate | FieldType::Hash) => {
                 self.replace_letters(from)
             }
-            FieldType::Names
-            | FieldType::DocumentType
-            | FieldType::CountryCode
-            | FieldType::Nationality
-            | FieldType::Alphabetic => self.replace_digits(from),
-            FieldType::Sex => from.replace('P', "F"),
+            (_, FieldType::DocumentType | FieldType::CountryCode | FieldType::Nationality) => self.replace_digits(from),
+            (CorrectionMode::Aggressive, FieldType::Names) => self.replace_digits(from),
             _ => from.to_string(),
         }
     }
 
     // Formatting logic
-    pub fn format(&self, from: &str, field_type: FieldType) -> Result<String, &'static str> {
+    pub fn format(&self, from: &str, field_type: FieldType) -> Result<String, MrzError> {
         match field_type {
-            FieldType::Names => Ok(self.names(from).join(" ")),
-            FieldType::Birthdate | FieldType::ExpiryDate => self.date(from),
-            FieldType::Sex => Ok(self.sex(from).to_string()),
+            FieldType::Names => Ok(Names::from_mrz(from).to_string()),
+            FieldType::Sex => Ok(Sex::parse(from)?.to_string()),
+            FieldType::DocumentNumber => Ok(from.trim_end_matches('<').to_string()),
             _ => Ok(from.to_string()),
         }
     }
 }
+
+// Error for a field that cannot be sliced from its line. The formatter reads a single line, so the
+// position is reported on line 0.
+fn out_of_range(from: &str, end_idx: usize) -> MrzError {
+    match from.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+        Some((column, character)) if column < end_idx => MrzError::InvalidCharacter {
+            line: 0,
+            column: from[..column].chars().count(),
+            character,
+        },
+        _ => MrzError::InvalidLineLength {
+            line: 0,
+            expected: end_idx,
+            actual: from.chars().count(),
+        },
+    }
+}
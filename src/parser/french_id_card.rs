@@ -0,0 +1,258 @@
+use crate::constants::mrz_utils::{FRENCH_ID_NUMBER_OF_CHARACTERS_PER_LINE, FRENCH_ID_PREFIX};
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{guided_corrections, CheckedSpan, CompositeSpan};
+use crate::parser::document::{DocumentKind, FrenchIdCardDocument, MrzDocument};
+use crate::parser::field_formatter::FieldFormatter;
+use crate::parser::field_formatter::FieldType::{
+    Birthdate, CountryCode, DocumentNumber, DocumentType, Hash, IssueDate, Names, OptionalData, Sex,
+};
+use crate::parser::mrz_field::MrzField;
+use crate::parser::options::ParserOptions;
+use crate::parser::parser::{IMRZParser, MRZResult};
+use crate::parser::repair::{repair_lines, LineTemplate};
+use crate::parser::strict::{check_strict, FieldSpec};
+use crate::utils::utils::calculate_check_digits;
+
+// Check-digit protected fields, in composite order: card number, birth date. The card number holds the
+// department code, which has a letter for Corsica (2A and 2B).
+const CHECKED_SPANS: [CheckedSpan; 2] = [
+    CheckedSpan {
+        line: 1,
+        start: 0,
+        length: 12,
+        numeric: false,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 27,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+];
+
+// The composite check digit covers the whole first line and the second line up to the sex
+const COMPOSITE_SPAN: CompositeSpan = CompositeSpan {
+    segments: &[(0, 0, 36), (1, 0, 35)],
+    line: 1,
+    position: 35,
+};
+
+// Fields checked in strict mode
+const FIELD_SPECS: [FieldSpec; 11] = [
+    FieldSpec::new(DocumentType, 0, 0, 2, false),
+    FieldSpec::new(CountryCode, 0, 2, 3, false),
+    FieldSpec::new(Names, 0, 5, 25, false),
+    FieldSpec::new(OptionalData, 0, 30, 3, false),
+    FieldSpec::new(Hash, 0, 33, 3, false),
+    FieldSpec::new(IssueDate, 1, 0, 4, false),
+    FieldSpec::new(DocumentNumber, 1, 0, 12, true),
+    FieldSpec::new(Names, 1, 13, 14, false),
+    FieldSpec::new(Birthdate, 1, 27, 6, true),
+    FieldSpec::new(Sex, 1, 34, 1, false),
+    FieldSpec::new(Hash, 1, 35, 1, false),
+];
+
+// Layout of the checked fields, used to repair line lengths and to decode OCR alternatives
+pub(crate) const TEMPLATE: LineTemplate = LineTemplate {
+    specs: &FIELD_SPECS,
+    spans: &CHECKED_SPANS,
+    composite: Some(&COMPOSITE_SPAN),
+};
+
+// Parser for French national identity cards issued before 2021, recognised by their `IDFRA` prefix
+#[derive(Default, Debug, Clone)]
+pub struct FrenchIdCard {
+    options: ParserOptions,
+}
+
+impl FrenchIdCard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        FrenchIdCard { options }
+    }
+
+    fn validate_all_check_digits(
+        &self, first_line: &[&MrzField], document_number: &MrzField, given_names: &MrzField, birthdate: &MrzField,
+        sex: &MrzField, final_check_digit: &MrzField,
+    ) -> Result<bool, MrzError> {
+        let mut composite_str: String = first_line
+            .iter()
+            .map(|field| field.raw_value.as_str())
+            .collect();
+        composite_str.push_str(&format!(
+            "{}{}{}{}{}{}",
+            document_number.raw_value,
+            document_number.check_digit,
+            given_names.raw_value,
+            birthdate.raw_value,
+            birthdate.check_digit,
+            sex.raw_value,
+        ));
+
+        let calculated_check_digit = calculate_check_digits(&composite_str)?;
+        Ok(document_number.is_valid && birthdate.is_valid && calculated_check_digit == final_check_digit.raw_value)
+    }
+}
+
+impl IMRZParser for FrenchIdCard {
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        let (mut lines, repairs) =
+            repair_lines(&self.options, input, 2, FRENCH_ID_NUMBER_OF_CHARACTERS_PER_LINE, &TEMPLATE)?;
+
+        if !lines[0].starts_with(FRENCH_ID_PREFIX) {
+            return Err(MrzError::UnknownFormat);
+        }
+
+        if self.options.strict {
+            check_strict(&lines, &FIELD_SPECS)?;
+        }
+
+        let mut guided = guided_corrections(&self.options, &mut lines, &CHECKED_SPANS, Some(&COMPOSITE_SPAN));
+
+        let first_line = &lines[0];
+        let second_line = &lines[1];
+        let formatter = FieldFormatter::new(self.options.correction, self.options.dates);
+
+        // Parse first line
+        let document_type = formatter.field(DocumentType, first_line, 0, 2, false)?;
+
+        let country_code = formatter.field(CountryCode, first_line, 2, 3, false)?;
+
+        // The surname and given names have a field each, without the primary and secondary identifier layout
+        let mut surname = formatter.field(Names, first_line, 5, 25, false)?;
+        surname.parsed = None;
+
+        let issuing_department = formatter.field(OptionalData, first_line, 30, 3, false)?;
+
+        let issuing_office = formatter.field(OptionalData, first_line, 33, 3, false)?;
+
+        // Parse second line. The card number starts with the issue year and month.
+        let issue_date = formatter.field(IssueDate, second_line, 0, 4, false)?;
+
+        let mut document_number = formatter.field(DocumentNumber, second_line, 0, 12, true)?;
+
+        let mut given_names = formatter.field(Names, second_line, 13, 14, false)?;
+        given_names.parsed = None;
+
+        let mut birthdate = formatter.field(Birthdate, second_line, 27, 6, true)?;
+
+        let sex = formatter.field(Sex, second_line, 34, 1, false)?;
+
+        let final_check_digit = formatter.field(Hash, second_line, 35, 1, false)?;
+
+        document_number.append_corrections(&mut guided[0]);
+        birthdate.append_corrections(&mut guided[1]);
+
+        let is_valid = self.validate_all_check_digits(
+            &[&document_type, &country_code, &surname, &issuing_department, &issuing_office],
+            &document_number,
+            &given_names,
+            &birthdate,
+            &sex,
+            &final_check_digit,
+        )?;
+
+        let document = MrzDocument::FrenchIdCard(FrenchIdCardDocument {
+            document_type,
+            issuing_country: country_code,
+            surname,
+            issuing_department,
+            issuing_office,
+            issue_date,
+            document_number,
+            given_names,
+            birth_date: birthdate,
+            sex,
+            composite_check_digit: final_check_digit,
+        });
+
+        MRZResult::new(&self.options, document, DocumentKind::IdCard, is_valid, repairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::mrz_field_name::{BIRTHDATE_FIELD, DOCUMENT_NUMBER_FIELD};
+    use crate::parser::correction::Substitution;
+    use crate::parser::mrz_date::MrzDate;
+    use crate::parser::options::CorrectionMode;
+
+    #[test]
+    fn test_parse_french_id_card() {
+        let mrz_string: Vec<String> = vec![
+            "IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013".to_string(),
+            "0506932020438CHRISTIANE<<NI2906209F3".to_string(),
+        ];
+
+        let result = FrenchIdCard::new().parse(&mrz_string).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.kind, DocumentKind::IdCard);
+        assert_eq!(result.issuing_state, "France");
+
+        let MrzDocument::FrenchIdCard(document) = &result.document else {
+            panic!("expected a French identity card");
+        };
+        assert_eq!(document.surname.value, "DOUEL");
+        assert_eq!(document.given_names.value, "CHRISTIANE NI");
+        assert_eq!(document.document_number.value, "050693202043");
+        assert_eq!(document.issuing_department.value, "932");
+        assert_eq!(document.issuing_office.value, "013");
+        assert_eq!(document.sex.value, "FEMALE");
+
+        let issue_date = document.issue_date.get_date().unwrap();
+        assert_eq!((issue_date.year, issue_date.month, issue_date.day), (Some(2005), Some(6), None));
+        assert_eq!(document.birth_date.get_date(), MrzDate::new(1929, 6, 20).as_ref());
+    }
+
+    #[test]
+    fn test_parse_french_id_card_corsica() {
+        // The card number holds the department 2A, and the `6` of the birth date was read as `G`. The letter
+        // of the department must not keep the guided search from correcting the birth date.
+        let mrz_string: Vec<String> = vec![
+            "IDFRADOUEL<<<<<<<<<<<<<<<<<<<<2A1013".to_string(),
+            "05062A1020437CHRISTIANE<<NI290G209F6".to_string(),
+        ];
+        let parser = FrenchIdCard::with_options(ParserOptions {
+            correction: CorrectionMode::CheckDigitGuided,
+            ..ParserOptions::default()
+        });
+
+        let result = parser.parse(&mrz_string).unwrap();
+        assert!(result.is_valid);
+        let number = result.get(DOCUMENT_NUMBER_FIELD).unwrap();
+        assert_eq!(number.value, "05062A102043");
+        assert!(number.corrections.is_empty());
+        assert_eq!(
+            result.get(BIRTHDATE_FIELD).unwrap().corrections,
+            vec![Substitution {
+                position: 3,
+                from: 'G',
+                to: '6'
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_french_id_card_invalid_composite() {
+        let mrz_string: Vec<String> = vec![
+            "IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932014".to_string(),
+            "0506932020438CHRISTIANE<<NI2906209F3".to_string(),
+        ];
+
+        let result = FrenchIdCard::new().parse(&mrz_string).unwrap();
+        assert!(result.get(DOCUMENT_NUMBER_FIELD).unwrap().is_valid);
+        assert!(!result.is_valid);
+
+        let td2 = vec![
+            "I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<".to_string(),
+            "D231458907UTO7408122F1204159<<<<<<<6".to_string(),
+        ];
+        assert_eq!(FrenchIdCard::new().parse(&td2).unwrap_err(), MrzError::UnknownFormat);
+    }
+}
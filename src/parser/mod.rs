@@ -1,6 +1,20 @@
+mod confidence;
+pub mod correction;
 mod field_formatter;
+pub mod document;
+pub mod driving_licence;
+pub mod french_id_card;
+pub mod mrz_date;
 pub mod mrz_field;
+pub mod mrv;
+pub mod names;
+pub mod ocr;
+pub mod options;
+pub mod repair;
+pub mod sex;
+pub mod strict;
 pub mod td1;
 pub mod td2;
 pub mod td3;
+pub mod warning;
 pub mod parser;
@@ -0,0 +1,216 @@
+use crate::constants::mrz_utils::{MRVA_NUMBER_OF_CHARACTERS_PER_LINE, MRVB_NUMBER_OF_CHARACTERS_PER_LINE};
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{guided_corrections, CheckedSpan};
+use crate::parser::document::{DocumentKind, MRVDocument, MrzDocument};
+use crate::parser::field_formatter::FieldFormatter;
+use crate::parser::field_formatter::FieldType::{
+    Birthdate, CountryCode, DocumentNumber, DocumentType, ExpiryDate, Names, Nationality, OptionalData, Sex,
+};
+use crate::parser::options::ParserOptions;
+use crate::parser::parser::{IMRZParser, MRZResult};
+use crate::parser::repair::{repair_lines, LineTemplate};
+use crate::parser::strict::{check_strict, FieldSpec};
+
+// Check-digit protected fields: document number, birth date, expiry date. Visas have no composite check digit.
+pub(crate) const CHECKED_SPANS: [CheckedSpan; 3] = [
+    CheckedSpan {
+        line: 1,
+        start: 0,
+        length: 9,
+        numeric: false,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 13,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 21,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+];
+
+// Fields checked in strict mode. The name field fills the rest of the first line.
+pub(crate) fn field_specs(names_length: usize) -> [FieldSpec; 8] {
+    [
+        FieldSpec::new(DocumentType, 0, 0, 2, false),
+        FieldSpec::new(CountryCode, 0, 2, 3, false),
+        FieldSpec::new(Names, 0, 5, names_length, false),
+        FieldSpec::new(DocumentNumber, 1, 0, 9, true),
+        FieldSpec::new(Nationality, 1, 10, 3, false),
+        FieldSpec::new(Birthdate, 1, 13, 6, true),
+        FieldSpec::new(Sex, 1, 20, 1, false),
+        FieldSpec::new(ExpiryDate, 1, 21, 6, true),
+    ]
+}
+
+// Parser for Format-A machine readable visas (2 lines of 44 characters)
+#[derive(Default, Debug, Clone)]
+pub struct MRVA {
+    options: ParserOptions,
+}
+
+impl MRVA {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        MRVA { options }
+    }
+}
+
+impl IMRZParser for MRVA {
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        parse_visa(&self.options, input, MRVA_NUMBER_OF_CHARACTERS_PER_LINE, MrzDocument::MRVA)
+    }
+}
+
+// Parser for Format-B machine readable visas (2 lines of 36 characters)
+#[derive(Default, Debug, Clone)]
+pub struct MRVB {
+    options: ParserOptions,
+}
+
+impl MRVB {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        MRVB { options }
+    }
+}
+
+impl IMRZParser for MRVB {
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        parse_visa(&self.options, input, MRVB_NUMBER_OF_CHARACTERS_PER_LINE, MrzDocument::MRVB)
+    }
+}
+
+// Both visa formats share their layout up to the optional data, which fills the rest of the second line
+// (16 characters for MRV-A, 8 for MRV-B)
+fn parse_visa(
+    options: &ParserOptions, input: &[String], line_length: usize, document: fn(MRVDocument) -> MrzDocument,
+) -> Result<MRZResult, MrzError> {
+    let names_length = line_length - 5;
+    let optional_data_length = line_length - 28;
+    let specs = field_specs(names_length);
+
+    let template = LineTemplate {
+        specs: &specs,
+        spans: &CHECKED_SPANS,
+        composite: None,
+    };
+    let (mut lines, repairs) = repair_lines(options, input, 2, line_length, &template)?;
+
+    if options.strict {
+        check_strict(&lines, &specs)?;
+    }
+
+    let mut guided = guided_corrections(options, &mut lines, &CHECKED_SPANS, None);
+
+    let first_line = &lines[0];
+    let second_line = &lines[1];
+    let formatter = FieldFormatter::new(options.correction, options.dates);
+
+    // Parse first line
+    let document_type = formatter.field(DocumentType, first_line, 0, 2, false)?;
+
+    let country_code = formatter.field(CountryCode, first_line, 2, 3, false)?;
+
+    let name = formatter.field(Names, first_line, 5, names_length, false)?;
+
+    // Parse second line
+    let mut document_number = formatter.field(DocumentNumber, second_line, 0, 9, true)?;
+
+    let nationality = formatter.field(Nationality, second_line, 10, 3, false)?;
+
+    let mut birthdate = formatter.field(Birthdate, second_line, 13, 6, true)?;
+
+    let sex = formatter.field(Sex, second_line, 20, 1, false)?;
+
+    let mut expiry_date = formatter.field(ExpiryDate, second_line, 21, 6, true)?;
+
+    let optional_data = formatter.field(OptionalData, second_line, 28, optional_data_length, false)?;
+
+    document_number.append_corrections(&mut guided[0]);
+    birthdate.append_corrections(&mut guided[1]);
+    expiry_date.append_corrections(&mut guided[2]);
+
+    let is_valid = document_number.is_valid && birthdate.is_valid && expiry_date.is_valid;
+
+    let kind = DocumentKind::from_document_type(&document_type.value);
+
+    let document = document(MRVDocument {
+        document_type,
+        issuing_country: country_code,
+        names: name,
+        document_number,
+        nationality,
+        birth_date: birthdate,
+        sex,
+        expiry_date,
+        optional_data,
+    });
+
+    MRZResult::new(options, document, kind, is_valid, repairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::mrz_field_name::{BIRTHDATE_FIELD, FINAL_CHECK_DIGIT_FIELD, OPTIONAL_DATA_1_FIELD};
+
+    // Specimens from ICAO 9303 Part 7, Appendix A
+    #[test]
+    fn test_parse_mrva() {
+        let mrz_string: Vec<String> = vec![
+            "V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L8988901C4XXX4009078F96121096ZE184226B<<<<<<".to_string(),
+        ];
+
+        let result = MRVA::new().parse(&mrz_string).unwrap();
+        assert!(result.is_visa);
+        assert_eq!(result.kind, DocumentKind::Visa);
+        assert!(result.is_valid);
+        assert!(matches!(result.document, MrzDocument::MRVA(_)));
+        assert!(result.get(FINAL_CHECK_DIGIT_FIELD).is_none());
+        assert_eq!(result.get(OPTIONAL_DATA_1_FIELD).unwrap().raw_value, "6ZE184226B<<<<<<");
+        assert_eq!(result.get(BIRTHDATE_FIELD).unwrap().value, "400907");
+    }
+
+    #[test]
+    fn test_parse_mrvb() {
+        let mrz_string: Vec<String> = vec![
+            "V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<".to_string(),
+            "L8988901C4XXX4009078F9612109<<<<<<<<".to_string(),
+        ];
+
+        let result = MRVB::new().parse(&mrz_string).unwrap();
+        assert_eq!(result.kind, DocumentKind::Visa);
+        assert!(result.is_valid);
+        assert!(matches!(result.document, MrzDocument::MRVB(_)));
+        assert_eq!(result.get(OPTIONAL_DATA_1_FIELD).unwrap().raw_value, "<<<<<<<<");
+
+        // MRV-A lines are too long for an MRV-B
+        let mrva_string: Vec<String> = vec![
+            "V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L8988901C4XXX4009078F96121096ZE184226B<<<<<<".to_string(),
+        ];
+        assert_eq!(
+            MRVB::new().parse(&mrva_string).unwrap_err(),
+            MrzError::InvalidLineLength {
+                line: 0,
+                expected: 36,
+                actual: 44
+            }
+        );
+    }
+}
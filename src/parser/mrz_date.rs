@@ -0,0 +1,288 @@
+use crate::error::mrz_error::MrzError;
+use crate::utils::utils::{current_date, days_in_month};
+use std::fmt;
+
+// A calendar date read from a YYMMDD field. Parts written as `<<` in the MRZ are unknown and
+// left as None, which makes the date partial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MrzDate {
+    pub year: Option<u16>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+}
+
+impl MrzDate {
+    // new: Creates a complete date, returning None if it does not exist in the calendar.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(Some(year), month) {
+            return None;
+        }
+        Some(MrzDate {
+            year: Some(year),
+            month: Some(month),
+            day: Some(day),
+        })
+    }
+
+    // is_complete: Returns true when year, month and day are all known.
+    pub fn is_complete(&self) -> bool {
+        self.year.is_some() && self.month.is_some() && self.day.is_some()
+    }
+
+    // is_partial: Returns true when at least one part of the date is unknown.
+    pub fn is_partial(&self) -> bool {
+        !self.is_complete()
+    }
+
+    // parse: Reads a YYMMDD value, resolving the two-digit year with the given century strategy.
+    pub(crate) fn parse(from: &str, kind: DateKind, options: &DateOptions) -> Result<Self, MrzError> {
+        let invalid = || MrzError::InvalidDate(from.to_string());
+
+        if from.len() != 6 || !from.is_ascii() {
+            return Err(invalid());
+        }
+
+        let part = |idx: usize| -> Result<Option<u8>, MrzError> {
+            match &from[idx..idx + 2] {
+                "<<" => Ok(None),
+                digits if digits.bytes().all(|b| b.is_ascii_digit()) => Ok(digits.parse::<u8>().ok()),
+                _ => Err(invalid()),
+            }
+        };
+
+        let (yy, month, day) = (part(0)?, part(2)?, part(4)?);
+
+        if let Some(month) = month {
+            if !(1..=12).contains(&month) {
+                return Err(invalid());
+            }
+        }
+
+        let year = yy.map(|yy| options.resolve_year(yy, month, day, kind));
+
+        if let Some(day) = day {
+            let max_day = match month {
+                Some(month) => days_in_month(year, month),
+                None => 31,
+            };
+            if day == 0 || day > max_day {
+                return Err(invalid());
+            }
+        }
+
+        Ok(MrzDate { year, month, day })
+    }
+}
+
+impl fmt::Display for MrzDate {
+    // Formats as YYYY-MM-DD, with unknown parts written as X
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{:04}", year)?,
+            None => write!(f, "XXXX")?,
+        }
+        for part in [self.month, self.day] {
+            match part {
+                Some(value) => write!(f, "-{:02}", value)?,
+                None => write!(f, "-XX")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Which date field is being read, as birth and expiry dates resolve their century differently.
+// Issue dates resolve like birth dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DateKind {
+    Birth,
+    Expiry,
+    Issue,
+}
+
+// How a two-digit year is expanded to a full year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CenturyPivot {
+    // Birth dates resolve to the latest year not after the reference date. Expiry dates resolve
+    // to the latest year at most `expiry_window` years after the reference date.
+    Relative { expiry_window: u16 },
+    // Two-digit years below the pivot fall in the 2000s, the others in the 1900s.
+    Fixed(u8),
+}
+
+impl Default for CenturyPivot {
+    fn default() -> Self {
+        CenturyPivot::Relative { expiry_window: 20 }
+    }
+}
+
+// Options controlling how birth and expiry dates are interpreted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateOptions {
+    pub pivot: CenturyPivot,
+    // The date considered as "today". Defaults to the current system date when None.
+    pub reference_date: Option<MrzDate>,
+}
+
+impl DateOptions {
+    // Years that would fall before year 0, with a reference date in the first century, are clamped to 0,
+    // and years past u16::MAX to u16::MAX.
+    fn resolve_year(&self, yy: u8, month: Option<u8>, day: Option<u8>, kind: DateKind) -> u16 {
+        let yy = yy as u16;
+
+        match self.pivot {
+            CenturyPivot::Fixed(pivot) => {
+                if yy < pivot as u16 {
+                    2000 + yy
+                } else {
+                    1900 + yy
+                }
+            }
+            CenturyPivot::Relative { expiry_window } => {
+                let reference = self.reference_date.unwrap_or_else(|| {
+                    let (year, month, day) = current_date();
+                    MrzDate {
+                        year: Some(year),
+                        month: Some(month),
+                        day: Some(day),
+                    }
+                });
+                let (yy, ref_year) = (yy as u32, reference.year.unwrap_or(2000) as u32);
+
+                let year = match kind {
+                    DateKind::Birth | DateKind::Issue => {
+                        let year = ref_year.saturating_sub((ref_year + 100 - yy) % 100);
+                        let later_in_year = match (month, reference.month) {
+                            (Some(month), Some(ref_month)) => {
+                                (month, day.unwrap_or(0)) > (ref_month, reference.day.unwrap_or(0))
+                            }
+                            _ => false,
+                        };
+                        if year == ref_year && later_in_year {
+                            year.saturating_sub(100)
+                        } else {
+                            year
+                        }
+                    }
+                    DateKind::Expiry => {
+                        let latest = ref_year + expiry_window as u32;
+                        latest.saturating_sub((latest + 100 - yy) % 100)
+                    }
+                };
+                u16::try_from(year).unwrap_or(u16::MAX)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relative_to(year: u16, month: u8, day: u8) -> DateOptions {
+        DateOptions {
+            pivot: CenturyPivot::default(),
+            reference_date: MrzDate::new(year, month, day),
+        }
+    }
+
+    #[test]
+    fn test_parse_birth_date() {
+        let options = relative_to(2024, 6, 15);
+        let date = MrzDate::parse("740812", DateKind::Birth, &options).unwrap();
+        assert_eq!(date, MrzDate::new(1974, 8, 12).unwrap());
+
+        // Same two-digit year as the reference, but later in the year
+        let date = MrzDate::parse("240616", DateKind::Birth, &options).unwrap();
+        assert_eq!(date.year, Some(1924));
+        let date = MrzDate::parse("240615", DateKind::Birth, &options).unwrap();
+        assert_eq!(date.year, Some(2024));
+    }
+
+    #[test]
+    fn test_parse_expiry_date() {
+        let options = relative_to(2024, 6, 15);
+        assert_eq!(
+            MrzDate::parse("120415", DateKind::Expiry, &options)
+                .unwrap()
+                .year,
+            Some(2012)
+        );
+        assert_eq!(
+            MrzDate::parse("340415", DateKind::Expiry, &options)
+                .unwrap()
+                .year,
+            Some(2034)
+        );
+        assert_eq!(
+            MrzDate::parse("961210", DateKind::Expiry, &options)
+                .unwrap()
+                .year,
+            Some(1996)
+        );
+    }
+
+    #[test]
+    fn test_early_reference_date() {
+        // Years before year 0 and after u16::MAX are clamped instead of overflowing
+        let options = relative_to(5, 1, 1);
+        let date = MrzDate::parse("740812", DateKind::Birth, &options).unwrap();
+        assert_eq!(date.year, Some(0));
+        let date = MrzDate::parse("050812", DateKind::Birth, &options).unwrap();
+        assert_eq!(date.year, Some(0));
+
+        let options = DateOptions {
+            pivot: CenturyPivot::Relative {
+                expiry_window: u16::MAX,
+            },
+            reference_date: MrzDate::new(u16::MAX, 1, 1),
+        };
+        let date = MrzDate::parse("120415", DateKind::Expiry, &options).unwrap();
+        assert_eq!(date.year, Some(u16::MAX));
+    }
+
+    #[test]
+    fn test_fixed_pivot() {
+        let options = DateOptions {
+            pivot: CenturyPivot::Fixed(30),
+            reference_date: None,
+        };
+        assert_eq!(
+            MrzDate::parse("290101", DateKind::Birth, &options)
+                .unwrap()
+                .year,
+            Some(2029)
+        );
+        assert_eq!(
+            MrzDate::parse("300101", DateKind::Expiry, &options)
+                .unwrap()
+                .year,
+            Some(1930)
+        );
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        let options = relative_to(2024, 6, 15);
+        assert!(MrzDate::parse("991399", DateKind::Birth, &options).is_err());
+        assert!(MrzDate::parse("990230", DateKind::Birth, &options).is_err());
+        assert!(MrzDate::parse("990229", DateKind::Birth, &options).is_err());
+        assert!(MrzDate::parse("000229", DateKind::Birth, &options).is_ok());
+        assert!(MrzDate::parse("99O101", DateKind::Birth, &options).is_err());
+    }
+
+    #[test]
+    fn test_partial_dates() {
+        let options = relative_to(2024, 6, 15);
+        let date = MrzDate::parse("74<<<<", DateKind::Birth, &options).unwrap();
+        assert!(date.is_partial());
+        assert_eq!(date.year, Some(1974));
+        assert_eq!(date.month, None);
+        assert_eq!(date.to_string(), "1974-XX-XX");
+
+        let date = MrzDate::parse("<<0812", DateKind::Birth, &options).unwrap();
+        assert_eq!(date.year, None);
+        assert_eq!(date.to_string(), "XXXX-08-12");
+    }
+}
@@ -1,11 +1,35 @@
+use crate::parser::confidence::correction_factor;
+use crate::parser::correction::Substitution;
+use crate::parser::mrz_date::MrzDate;
+use crate::parser::names::Names;
+use crate::parser::sex::Sex;
 use crate::utils::utils::is_value_valid;
 
+// Typed interpretation of a field value, for the fields that have one
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value", rename_all = "snake_case"))]
+pub enum ParsedValue {
+    Date(MrzDate),
+    Sex(Sex),
+    Names(Names),
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MrzField {
     pub value: String,
     pub raw_value: String,
     pub check_digit: String,
+    // Whether the check digit following the field matches its raw value. Always true for fields without a
+    // check digit. The overall validity, including the composite check digit, is MRZResult::is_valid.
     pub is_valid: bool,
+    pub parsed: Option<ParsedValue>,
+    // OCR corrections applied to the raw value, the check digit being at position raw_value.len()
+    pub corrections: Vec<Substitution>,
+    // Confidence in the value between 0 and 1, from its check digit, character classes, country code lookup
+    // and corrections
+    pub confidence: f64,
 }
 
 impl MrzField {
@@ -15,6 +39,9 @@ impl MrzField {
             raw_value,
             check_digit,
             is_valid,
+            parsed: None,
+            corrections: Vec::new(),
+            confidence: 1.0,
         }
     }
 
@@ -32,6 +59,30 @@ impl MrzField {
         self.value.clone()
     }
 
+    // get_date: Returns the parsed date of a birth or expiry date field.
+    pub fn get_date(&self) -> Option<&MrzDate> {
+        match &self.parsed {
+            Some(ParsedValue::Date(date)) => Some(date),
+            _ => None,
+        }
+    }
+
+    // get_identifiers: Returns the primary and secondary identifiers of a name field.
+    pub fn get_identifiers(&self) -> Option<&Names> {
+        match &self.parsed {
+            Some(ParsedValue::Names(names)) => Some(names),
+            _ => None,
+        }
+    }
+
+    // get_sex: Returns the parsed sex of a sex field.
+    pub fn get_sex(&self) -> Option<Sex> {
+        match &self.parsed {
+            Some(ParsedValue::Sex(sex)) => Some(*sex),
+            _ => None,
+        }
+    }
+
     // get_raw_value: Returns the field value as a string.
     pub fn get_raw_value(&self) -> &str {
         &self.raw_value
@@ -42,13 +93,17 @@ impl MrzField {
         &self.check_digit
     }
 
-    // is_valid: Performs field validity check.
-    pub fn is_valid(&mut self) -> bool {
+    // append_corrections: Records corrections made outside of the field formatter, lowering the confidence.
+    pub(crate) fn append_corrections(&mut self, corrections: &mut Vec<Substitution>) {
+        self.confidence *= correction_factor(corrections.len());
+        self.corrections.append(corrections);
+    }
+
+    // is_valid: Checks the check digit of the field against its raw value. A field left empty may use a
+    // filler as check digit.
+    pub fn is_valid(&self) -> bool {
         if self.check_digit == "<" {
-            if !self.raw_value.trim_matches('<').is_empty() {
-                return false;
-            }
-            self.check_digit = "0".to_string();
+            return self.raw_value.trim_matches('<').is_empty();
         } else if self.check_digit.parse::<i32>().is_err() {
             return false;
         }
@@ -0,0 +1,96 @@
+use std::fmt;
+
+// The name of the holder, split into the primary identifier (the surname) and the components of the
+// secondary identifier (the given names)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Names {
+    // Components of the primary identifier separated by spaces, e.g. "VAN DER BERG"
+    pub primary: String,
+    pub secondary: Vec<String>,
+    // The name filled the whole field without a trailing filler, so it may have been cut by the issuer
+    pub truncated: bool,
+}
+
+impl Names {
+    // from_mrz: Reads a name field. The primary identifier ends at the first `<<`, and every component
+    // after it, including those following further `<<` separators, is part of the secondary identifier.
+    pub fn from_mrz(from: &str) -> Self {
+        let (primary, secondary) = from.split_once("<<").unwrap_or((from, ""));
+
+        Names {
+            primary: components(primary).join(" "),
+            secondary: components(secondary)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            truncated: !from.is_empty() && !from.ends_with('<'),
+        }
+    }
+
+    // given_names: Returns the components of the secondary identifier separated by spaces.
+    pub fn given_names(&self) -> String {
+        self.secondary.join(" ")
+    }
+
+    // given_names_first: Returns the name in the order used in most western countries, e.g.
+    // "ANNA MARIA ERIKSSON".
+    pub fn given_names_first(&self) -> String {
+        join_non_empty(&self.given_names(), &self.primary)
+    }
+
+    // primary_first: Returns the name with the primary identifier first, as written in the MRZ, e.g.
+    // "ERIKSSON ANNA MARIA". This is also the value of the name field.
+    pub fn primary_first(&self) -> String {
+        join_non_empty(&self.primary, &self.given_names())
+    }
+}
+
+impl fmt::Display for Names {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.primary_first())
+    }
+}
+
+fn components(from: &str) -> Vec<&str> {
+    from.split('<')
+        .filter(|component| !component.is_empty())
+        .collect()
+}
+
+fn join_non_empty(first: &str, second: &str) -> String {
+    match (first.is_empty(), second.is_empty()) {
+        (_, true) => first.to_string(),
+        (true, false) => second.to_string(),
+        (false, false) => format!("{} {}", first, second),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_from_mrz() {
+        let names = Names::from_mrz("ERIKSSON<<ANNA<MARIA<<<<<<<<<<");
+        assert_eq!(names.primary, "ERIKSSON");
+        assert_eq!(names.secondary, vec!["ANNA", "MARIA"]);
+        assert!(!names.truncated);
+        assert_eq!(names.given_names_first(), "ANNA MARIA ERIKSSON");
+        assert_eq!(names.to_string(), "ERIKSSON ANNA MARIA");
+
+        // Extra `<<` separators still separate given names
+        let names = Names::from_mrz("VAN<DER<BERG<<JAN<<PIETER<<<<<");
+        assert_eq!(names.primary, "VAN DER BERG");
+        assert_eq!(names.secondary, vec!["JAN", "PIETER"]);
+
+        // A primary identifier alone, and a name cut at the end of the field
+        let names = Names::from_mrz("ERIKSSON<<<<<<");
+        assert!(names.secondary.is_empty());
+        assert_eq!(names.given_names_first(), "ERIKSSON");
+
+        let names = Names::from_mrz("NILAVADHANANANDA<<CHAYAPA<DEJ");
+        assert_eq!(names.secondary, vec!["CHAYAPA", "DEJ"]);
+        assert!(names.truncated);
+    }
+}
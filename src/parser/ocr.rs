@@ -0,0 +1,473 @@
+use crate::constants::mrz_utils::{
+    ALPHABET_MAPPING, DRIVING_LICENCE_NUMBER_OF_CHARACTERS_PER_LINE, FRENCH_ID_PREFIX,
+    TYPE1_NUMBER_OF_CHARACTERS_PER_LINE, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+    WEIGHTS,
+};
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{CheckedSpan, CompositeSpan};
+use crate::parser::field_formatter::FieldType;
+use crate::parser::parser::check_lines;
+use crate::parser::repair::LineTemplate;
+use crate::parser::strict::is_allowed;
+use crate::parser::{driving_licence, french_id_card, mrv, td1, td2, td3};
+use crate::MRZParser;
+use std::collections::HashMap;
+
+// Number of partial readings kept at each position by OcrInput::decode
+const DEFAULT_BEAM_WIDTH: usize = 256;
+
+// Probability given to each MRZ character at a position without any alternative
+const UNREADABLE_PROBABILITY: f64 = 1.0 / 37.0;
+
+const MRZ_CHARACTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789<";
+
+// A character the OCR engine may have read at a position, with its probability
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alternative {
+    pub character: char,
+    pub probability: f64,
+}
+
+// OCR output for an MRZ: for each line, the top alternatives read at each position. A position without
+// any alternative is unreadable, and may hold any character allowed by its field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OcrInput {
+    pub lines: Vec<Vec<Vec<Alternative>>>,
+}
+
+// The reading of an OcrInput chosen by the beam search
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrReading {
+    pub lines: Vec<String>,
+    // Joint probability of the chosen characters
+    pub probability: f64,
+    // Whether the reading satisfies every check digit and field character class of its format. When no
+    // reading does, the most likely character is taken at each position.
+    pub satisfies_constraints: bool,
+}
+
+impl OcrReading {
+    // parser: Returns a parser over the chosen lines.
+    pub fn parser(&self) -> MRZParser {
+        MRZParser::new_mrz_line_parser(self.lines.clone())
+    }
+}
+
+// A check digit, at a position of the concatenated lines, and the weight of each position it covers
+struct CheckConstraint {
+    covered: Vec<(usize, i32)>,
+    position: usize,
+    // Field check digits may be a filler when the whole field is
+    filler_allowed: bool,
+}
+
+// A reading of the positions decoded so far, with the running sum of each check digit
+#[derive(Debug, Clone)]
+struct Partial {
+    log_probability: f64,
+    characters: String,
+    sums: Vec<u8>,
+    fillers_only: Vec<bool>,
+}
+
+impl OcrInput {
+    pub fn new(lines: Vec<Vec<Vec<Alternative>>>) -> Self {
+        OcrInput { lines }
+    }
+
+    // decode: Returns the most likely reading satisfying every check digit and field character class of
+    // the format, searched with the default beam width.
+    pub fn decode(&self) -> Result<OcrReading, MrzError> {
+        self.decode_with_beam_width(DEFAULT_BEAM_WIDTH)
+    }
+
+    // decode_with_beam_width: Returns the most likely reading satisfying every check digit and field
+    // character class of the format, keeping at most `beam_width` partial readings at each position.
+    //
+    // The format is detected from the number and length of the lines and the most likely first
+    // characters, as MRZParser does. Partial readings with the same pending check digit sums have the same
+    // possible endings, so only the most likely one of them is kept.
+    pub fn decode_with_beam_width(&self, beam_width: usize) -> Result<OcrReading, MrzError> {
+        let top_lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|alternatives| most_likely(alternatives).0)
+                    .collect()
+            })
+            .collect();
+
+        let line_length = match top_lines.len() {
+            1 => DRIVING_LICENCE_NUMBER_OF_CHARACTERS_PER_LINE,
+            2 if top_lines[0].chars().count() == TYPE2_NUMBER_OF_CHARACTERS_PER_LINE => {
+                TYPE2_NUMBER_OF_CHARACTERS_PER_LINE
+            }
+            2 => TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+            3 => TYPE1_NUMBER_OF_CHARACTERS_PER_LINE,
+            count => return Err(MrzError::InvalidLineCount(count)),
+        };
+        let replaced: Vec<String> = top_lines
+            .iter()
+            .map(|line| {
+                line.chars()
+                    .map(|ch| if ch.is_ascii() { ch } else { '<' })
+                    .collect()
+            })
+            .collect();
+        check_lines(&replaced, top_lines.len(), line_length)?;
+
+        let visa_specs = mrv::field_specs(line_length - 5);
+        let visa = LineTemplate {
+            specs: &visa_specs,
+            spans: &mrv::CHECKED_SPANS,
+            composite: None,
+        };
+        let template = match (top_lines.len(), line_length) {
+            (1, _) => &driving_licence::TEMPLATE,
+            (3, _) => &td1::TEMPLATE,
+            (_, _) if top_lines[0].starts_with('V') => &visa,
+            (_, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE) if top_lines[0].starts_with(FRENCH_ID_PREFIX) => {
+                &french_id_card::TEMPLATE
+            }
+            (_, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE) => &td2::TEMPLATE,
+            _ => &td3::TEMPLATE,
+        };
+
+        // A long TD1 document number continues in the optional data, as TD1 reads it
+        let spans = match top_lines.len() {
+            3 => td1::checked_spans(&replaced[0]).to_vec(),
+            _ => template.spans.to_vec(),
+        };
+
+        let reading = self
+            .search(template, &spans, line_length, beam_width)
+            .unwrap_or_else(|| self.unconstrained());
+        Ok(reading)
+    }
+
+    // Beam search over the readings allowed by the template, None if there is none
+    fn search(
+        &self, template: &LineTemplate, spans: &[CheckedSpan], line_length: usize, beam_width: usize,
+    ) -> Option<OcrReading> {
+        let total = self.lines.len() * line_length;
+        let constraints = constraints(spans, template.composite, line_length);
+
+        // Positions that must, or must not, hold a filler
+        let mut fillers: Vec<Option<bool>> = vec![None; total];
+        for span in spans.iter().filter(|span| span.overflow > 0) {
+            mark_overflow(span, line_length, &mut fillers);
+        }
+
+        let mut covering: Vec<Vec<(usize, i32)>> = vec![Vec::new(); total];
+        let mut checking: Vec<Vec<usize>> = vec![Vec::new(); total];
+        for (idx, constraint) in constraints.iter().enumerate() {
+            for &(position, weight) in &constraint.covered {
+                covering[position].push((idx, weight));
+            }
+            checking[constraint.position].push(idx);
+        }
+
+        let mut field_types: Vec<Vec<FieldType>> = vec![Vec::new(); total];
+        for spec in template.specs {
+            for column in spec.start..spec.start + spec.length {
+                field_types[spec.line * line_length + column].push(spec.field_type);
+            }
+        }
+
+        let mut beam = vec![Partial {
+            log_probability: 0.0,
+            characters: String::new(),
+            sums: vec![0; constraints.len()],
+            fillers_only: vec![true; constraints.len()],
+        }];
+
+        for position in 0..total {
+            let alternatives = readable_alternatives(&self.lines[position / line_length][position % line_length]);
+            let allowed = |ch: char| {
+                MRZ_CHARACTERS.contains(ch)
+                    && field_types[position]
+                        .iter()
+                        .all(|&field_type| is_allowed(field_type, ch))
+                    && (checking[position].is_empty() || ch.is_ascii_digit() || ch == '<')
+                    && fillers[position].is_none_or(|filler| filler == (ch == '<'))
+            };
+
+            // Best partial reading for each set of pending check digit sums
+            let mut next: HashMap<(Vec<u8>, Vec<bool>), Partial> = HashMap::new();
+            for partial in &beam {
+                for &(ch, probability) in alternatives.iter().filter(|(ch, _)| allowed(*ch)) {
+                    let Some(extended) = extend(partial, ch, probability, position, &constraints, &covering, &checking)
+                    else {
+                        continue;
+                    };
+                    let key = (extended.sums.clone(), extended.fillers_only.clone());
+                    if next
+                        .get(&key)
+                        .is_none_or(|best| best.log_probability < extended.log_probability)
+                    {
+                        next.insert(key, extended);
+                    }
+                }
+            }
+
+            beam = next.into_values().collect();
+            beam.sort_by(|a, b| {
+                b.log_probability
+                    .total_cmp(&a.log_probability)
+                    .then_with(|| a.characters.cmp(&b.characters))
+            });
+            beam.truncate(beam_width.max(1));
+        }
+
+        let best = beam.into_iter().next()?;
+        Some(OcrReading {
+            lines: split_lines(&best.characters, line_length),
+            probability: best.log_probability.exp(),
+            satisfies_constraints: true,
+        })
+    }
+
+    // The most likely character at each position, whatever the constraints
+    fn unconstrained(&self) -> OcrReading {
+        let mut probability = 1.0;
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|alternatives| {
+                        let (ch, p) = most_likely(alternatives);
+                        probability *= p;
+                        ch
+                    })
+                    .collect()
+            })
+            .collect();
+
+        OcrReading {
+            lines,
+            probability,
+            satisfies_constraints: false,
+        }
+    }
+}
+
+// The check digits of the spans and composite, with the positions they cover in the concatenated lines
+fn constraints(spans: &[CheckedSpan], composite: Option<&CompositeSpan>, line_length: usize) -> Vec<CheckConstraint> {
+    let mut constraints: Vec<CheckConstraint> = spans
+        .iter()
+        .map(|span| {
+            let offset = span.line * line_length;
+            CheckConstraint {
+                covered: weighted(span.columns().map(|column| offset + column).collect()),
+                position: offset + span.check_column(),
+                // A value continued after a filler is not empty
+                filler_allowed: span.overflow == 0,
+            }
+        })
+        .collect();
+
+    if let Some(composite) = composite {
+        let positions = composite
+            .segments
+            .iter()
+            .flat_map(|&(line, start, end)| (line * line_length + start)..(line * line_length + end))
+            .collect();
+        constraints.push(CheckConstraint {
+            covered: weighted(positions),
+            position: composite.line * line_length + composite.position,
+            filler_allowed: false,
+        });
+    }
+
+    constraints
+}
+
+// Marks the positions keeping a value continued after a filler read the same way, as td1::overflow_length
+// reads it: the filler in place of the check digit, the overflow and its check digit, and the filler
+// ending them.
+fn mark_overflow(span: &CheckedSpan, line_length: usize, fillers: &mut [Option<bool>]) {
+    let offset = span.line * line_length;
+    let filler = offset + span.start + span.length;
+    let position = offset + span.check_column();
+
+    fillers[filler] = Some(true);
+    for overflow in &mut fillers[filler + 1..=position] {
+        *overflow = Some(false);
+    }
+    if position + 1 < offset + line_length {
+        fillers[position + 1] = Some(true);
+    }
+}
+
+fn weighted(positions: Vec<usize>) -> Vec<(usize, i32)> {
+    positions
+        .into_iter()
+        .enumerate()
+        .map(|(idx, position)| (position, WEIGHTS[idx % WEIGHTS.len()]))
+        .collect()
+}
+
+// Appends a character to a partial reading, or returns None if it does not match a check digit
+fn extend(
+    partial: &Partial, ch: char, probability: f64, position: usize, constraints: &[CheckConstraint],
+    covering: &[Vec<(usize, i32)>], checking: &[Vec<usize>],
+) -> Option<Partial> {
+    let mut extended = partial.clone();
+
+    for &idx in &checking[position] {
+        let matches = match ch {
+            '<' => constraints[idx].filler_allowed && extended.fillers_only[idx],
+            ch => ch.to_digit(10) == Some(extended.sums[idx] as u32),
+        };
+        if !matches {
+            return None;
+        }
+        // The check digit is not needed anymore: reset it so that it does not split equivalent readings
+        extended.sums[idx] = 0;
+        extended.fillers_only[idx] = true;
+    }
+
+    for &(idx, weight) in &covering[position] {
+        extended.sums[idx] = ((extended.sums[idx] as i32 + character_value(ch) * weight) % 10) as u8;
+        if ch != '<' {
+            extended.fillers_only[idx] = false;
+        }
+    }
+
+    extended.log_probability += probability.ln();
+    extended.characters.push(ch);
+    Some(extended)
+}
+
+fn character_value(ch: char) -> i32 {
+    match ch {
+        '<' => 0,
+        ch if ch.is_ascii_digit() => ch as i32 - '0' as i32,
+        ch => ALPHABET_MAPPING.get(&ch).copied().unwrap_or(0),
+    }
+}
+
+// The alternatives of a position with a non-zero probability, or every MRZ character if there is none
+fn readable_alternatives(alternatives: &[Alternative]) -> Vec<(char, f64)> {
+    let readable: Vec<(char, f64)> = alternatives
+        .iter()
+        .filter(|alternative| alternative.probability > 0.0)
+        .map(|alternative| (alternative.character, alternative.probability))
+        .collect();
+    if readable.is_empty() {
+        MRZ_CHARACTERS
+            .chars()
+            .map(|ch| (ch, UNREADABLE_PROBABILITY))
+            .collect()
+    } else {
+        readable
+    }
+}
+
+fn most_likely(alternatives: &[Alternative]) -> (char, f64) {
+    alternatives
+        .iter()
+        .filter(|alternative| alternative.probability > 0.0)
+        .max_by(|a, b| a.probability.total_cmp(&b.probability))
+        .map_or(('<', UNREADABLE_PROBABILITY), |alternative| (alternative.character, alternative.probability))
+}
+
+fn split_lines(characters: &str, line_length: usize) -> Vec<String> {
+    characters
+        .as_bytes()
+        .chunks(line_length)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads every character with certainty, except at the given (line, column) positions
+    fn input(lines: &[&str], uncertain: &[(usize, usize, Vec<Alternative>)]) -> OcrInput {
+        let mut input = OcrInput::new(
+            lines
+                .iter()
+                .map(|line| {
+                    line.chars()
+                        .map(|character| {
+                            vec![Alternative {
+                                character,
+                                probability: 1.0,
+                            }]
+                        })
+                        .collect()
+                })
+                .collect(),
+        );
+        for (line, column, alternatives) in uncertain {
+            input.lines[*line][*column] = alternatives.clone();
+        }
+        input
+    }
+
+    fn alternatives(alternatives: &[(char, f64)]) -> Vec<Alternative> {
+        alternatives
+            .iter()
+            .map(|&(character, probability)| Alternative { character, probability })
+            .collect()
+    }
+
+    const TD3: [&str; 2] =
+        ["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<10"];
+
+    #[test]
+    fn test_decode_with_check_digits() {
+        // The OCR engine prefers `8` for the `C` of the document number, which breaks its check digit
+        let ocr = input(&TD3, &[(1, 7, alternatives(&[('8', 0.6), ('C', 0.4)]))]);
+        let reading = ocr.decode().unwrap();
+        assert!(reading.satisfies_constraints);
+        assert_eq!(reading.lines, TD3);
+        assert!((reading.probability - 0.4).abs() < 1e-9);
+        assert!(reading.parser().parse().unwrap().is_valid);
+    }
+
+    #[test]
+    fn test_decode_with_character_classes() {
+        // A letter is not allowed in a date, and an unreadable check digit is recovered
+        let ocr = input(&TD3, &[(1, 15, alternatives(&[('O', 0.7), ('0', 0.3)])), (1, 19, Vec::new())]);
+        let reading = ocr.decode().unwrap();
+        assert_eq!(reading.lines, TD3);
+        assert!((reading.probability - 0.3 * UNREADABLE_PROBABILITY).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_decode_long_document_number() {
+        // The document number D23145890734 continues in the optional data, after the filler at column 14
+        let td1 =
+            ["I<UTOD23145890<7349<<<<<<<<<<<", "3407127M9507122UTO<<<<<<<<<<<2", "STEVENSON<<PETER<JOHN<<<<<<<<<"];
+        let ocr = input(&td1, &[(0, 16, alternatives(&[('8', 0.6), ('3', 0.4)]))]);
+        let reading = ocr.decode().unwrap();
+        assert!(reading.satisfies_constraints);
+        assert_eq!(reading.lines, td1);
+        assert!((reading.probability - 0.4).abs() < 1e-9);
+        assert!(reading.parser().parse().unwrap().is_valid);
+    }
+
+    #[test]
+    fn test_decode_without_valid_reading() {
+        let ocr = input(&TD3, &[(1, 9, alternatives(&[('7', 0.9), ('8', 0.1)]))]);
+        let reading = ocr.decode().unwrap();
+        assert!(!reading.satisfies_constraints);
+        assert_eq!(reading.lines[1], "L898902C37UTO7408122F1204159ZE184226B<<<<<10");
+        assert!((reading.probability - 0.9).abs() < 1e-9);
+
+        let ocr = input(&[&TD3[1][..40]], &[]);
+        assert_eq!(
+            ocr.decode().unwrap_err(),
+            MrzError::InvalidLineLength {
+                line: 0,
+                expected: 30,
+                actual: 40
+            }
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use crate::parser::mrz_date::DateOptions;
+
+// How OCR errors are corrected while parsing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CorrectionMode {
+    // Values are read as they are.
+    Off,
+    // Letters are replaced with digits in dates and check digits, and digits with letters in the
+    // document type and country codes.
+    Conservative,
+    // Conservative corrections, plus digits replaced with letters in names.
+    #[default]
+    Aggressive,
+    // Conservative corrections, plus the check-digit guided search (see CheckDigitCorrector) over the
+    // document number, dates and personal number.
+    CheckDigitGuided,
+}
+
+// Options shared by all the document parsers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParserOptions {
+    pub dates: DateOptions,
+    pub correction: CorrectionMode,
+    // Rejects input outside of the ICAO 9303 character set or with misplaced filler, before any correction.
+    pub strict: bool,
+    // Brings lines up to two characters too short or too long to the length of their format, by inserting or
+    // removing the fillers that best realign the fixed-position fields and check digits. The repairs are
+    // listed in MRZResult::repairs.
+    pub repair_line_length: bool,
+}
@@ -1,14 +1,130 @@
+use crate::constants::countries::{lookup_country, Country};
+use crate::constants::mrz_field_name::{COUNTRY_CODE_FIELD, NATIONALITY_FIELD};
+use crate::constants::mrz_utils::lookup_code;
+use crate::error::mrz_error::MrzError;
+use crate::parser::document::{DocumentKind, MrzDocument};
 use crate::parser::mrz_field::MrzField;
+use crate::parser::options::ParserOptions;
+use crate::parser::repair::LineRepair;
+use crate::parser::warning::{check_codes, ParseWarning};
 use std::collections::HashMap;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MRZResult {
     pub is_visa: bool,
+    pub kind: DocumentKind,
     pub is_valid: bool,
-    pub fields: HashMap<String, MrzField>,
+    pub document: MrzDocument,
     pub issuing_state: String,
+    // Lines brought to the length of the format when ParserOptions::repair_line_length is set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub repairs: Vec<LineRepair>,
+    // Problems that did not prevent parsing, such as an unknown issuing state or nationality code
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl MRZResult {
+    // new: Assembles the result of a parsed document, naming its issuing state and checking its issuing
+    // state and nationality codes against the ICAO 9303 table.
+    pub(crate) fn new(
+        options: &ParserOptions, document: MrzDocument, kind: DocumentKind, is_valid: bool, repairs: Vec<LineRepair>,
+    ) -> Result<Self, MrzError> {
+        let country_code = document.get(COUNTRY_CODE_FIELD);
+        let issuing_state = country_code
+            .and_then(|field| lookup_code(&field.value))
+            .map_or("Unknown", |code| code.name)
+            .to_string();
+
+        let codes: Vec<(&'static str, &MrzField)> = [COUNTRY_CODE_FIELD, NATIONALITY_FIELD]
+            .into_iter()
+            .filter_map(|name| document.get(name).map(|field| (name, field)))
+            .collect();
+        let warnings = check_codes(options, &codes)?;
+
+        Ok(MRZResult {
+            is_visa: matches!(document, MrzDocument::MRVA(_) | MrzDocument::MRVB(_)),
+            kind,
+            is_valid,
+            document,
+            issuing_state,
+            repairs,
+            warnings,
+        })
+    }
+
+    // fields: Returns a map view of the parsed fields keyed by the names in constants::mrz_field_name.
+    pub fn fields(&self) -> HashMap<&str, &MrzField> {
+        self.document.fields()
+    }
+
+    // get: Returns the field with the given name, or None if the document format does not have it.
+    pub fn get(&self, name: &str) -> Option<&MrzField> {
+        self.document.get(name)
+    }
+
+    // issuing_country: Returns the ISO 3166 country of the issuing state, or None for an organisation or an
+    // unknown code.
+    pub fn issuing_country(&self) -> Option<Country> {
+        self.get(COUNTRY_CODE_FIELD)
+            .and_then(|field| lookup_country(&field.value))
+    }
+
+    // nationality: Returns the ISO 3166 country of the holder's nationality, or None when the format has no
+    // nationality or the code is not a country, e.g. for stateless persons and refugees.
+    pub fn nationality(&self) -> Option<Country> {
+        self.get(NATIONALITY_FIELD)
+            .and_then(|field| lookup_country(&field.value))
+    }
+
+    // quality: Returns the overall confidence in the result between 0 and 1: the lowest field confidence,
+    // halved when the check digits do not all match and multiplied by the confidence of any line repair.
+    // A low quality is a hint to rescan the document.
+    pub fn quality(&self) -> f64 {
+        let lowest = self
+            .fields()
+            .values()
+            .map(|field| field.confidence)
+            .fold(1.0, f64::min);
+        let validity = if self.is_valid { 1.0 } else { 0.5 };
+        let repairs: f64 = self
+            .repairs
+            .iter()
+            .map(|repair| repair.confidence)
+            .product();
+        lowest * validity * repairs
+    }
 }
 
 pub trait IMRZParser {
-    fn parse(&self, input: &Vec<String>) -> Result<MRZResult, &'static str>;
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError>;
+}
+
+// check_lines: Checks that the input has the expected number of lines, each with the expected number of
+// characters, all of them ASCII so that fields can be sliced by position.
+pub(crate) fn check_lines(input: &[String], count: usize, length: usize) -> Result<(), MrzError> {
+    if input.len() != count {
+        return Err(MrzError::InvalidLineCount(input.len()));
+    }
+
+    for (idx, line) in input.iter().enumerate() {
+        let actual = line.chars().count();
+        if actual != length {
+            return Err(MrzError::InvalidLineLength {
+                line: idx,
+                expected: length,
+                actual,
+            });
+        }
+        if let Some((column, character)) = line.chars().enumerate().find(|(_, ch)| !ch.is_ascii()) {
+            return Err(MrzError::InvalidCharacter {
+                line: idx,
+                column,
+                character,
+            });
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,377 @@
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{CheckedSpan, CompositeSpan};
+use crate::parser::options::ParserOptions;
+use crate::parser::parser::check_lines;
+use crate::parser::strict::{strict_violations, FieldSpec};
+use crate::utils::utils::{calculate_check_digits, is_value_valid};
+use std::collections::HashMap;
+
+// Number of fillers that may be inserted in or removed from a line
+pub(crate) const MAX_LENGTH_REPAIR: usize = 2;
+
+// A filler inserted in or removed from a line to bring it to the length of its format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "edit", rename_all = "snake_case"))]
+pub enum LengthEdit {
+    // A `<` was inserted, at this column of the repaired line.
+    Inserted { column: usize },
+    // A `<` was removed, from this column of the original line.
+    Removed { column: usize },
+}
+
+// A line whose length was repaired, with the confidence in the repair between 0 and 1
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineRepair {
+    pub line: usize,
+    pub original: String,
+    pub edits: Vec<LengthEdit>,
+    pub confidence: f64,
+}
+
+// Fixed-position fields and check digits a repaired line is aligned against
+pub(crate) struct LineTemplate<'a> {
+    pub specs: &'a [FieldSpec],
+    pub spans: &'a [CheckedSpan],
+    pub composite: Option<&'a CompositeSpan>,
+}
+
+// repair_lines: Checks the number and length of the lines like check_lines. When length repair is enabled,
+// lines up to MAX_LENGTH_REPAIR characters off are first brought to the expected length by inserting or
+// removing fillers, picking the edits that best fit the template.
+pub(crate) fn repair_lines(
+    options: &ParserOptions, input: &[String], count: usize, length: usize, template: &LineTemplate,
+) -> Result<(Vec<String>, Vec<LineRepair>), MrzError> {
+    let mut lines = input.to_vec();
+    let mut repairs = Vec::new();
+
+    if options.repair_line_length && lines.len() == count {
+        for (idx, line) in lines.iter_mut().enumerate() {
+            let actual = line.chars().count();
+            if actual == length || actual.abs_diff(length) > MAX_LENGTH_REPAIR || !line.is_ascii() {
+                continue;
+            }
+            if let Some((repaired, edits, confidence)) = best_repair(idx, line, length, template) {
+                repairs.push(LineRepair {
+                    line: idx,
+                    original: std::mem::replace(line, repaired),
+                    edits,
+                    confidence,
+                });
+            }
+        }
+    }
+
+    check_lines(&lines, count, length)?;
+    Ok((lines, repairs))
+}
+
+// check_repairable_lines: Checks the lines like check_lines, allowing lengths up to MAX_LENGTH_REPAIR
+// characters off.
+pub(crate) fn check_repairable_lines(input: &[String], count: usize, length: usize) -> Result<(), MrzError> {
+    let fitted: Vec<String> = input
+        .iter()
+        .map(|line| {
+            let mut chars: Vec<char> = line.chars().collect();
+            if chars.len().abs_diff(length) <= MAX_LENGTH_REPAIR {
+                chars.resize(length, '<');
+            }
+            chars.into_iter().collect()
+        })
+        .collect();
+    check_lines(&fitted, count, length)
+}
+
+// How well a candidate line fits the template, best first: strict violations, then check digits not matching
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Fit {
+    violations: usize,
+    mismatches: usize,
+}
+
+// A repaired line, with the edits that produced it and how unlikely they are as OCR errors
+struct Candidate {
+    fit: Fit,
+    checks: usize,
+    edits: Vec<LengthEdit>,
+    penalty: usize,
+}
+
+// Tries every way of inserting or removing the missing or extra fillers and returns the best fitting line,
+// its edits and the confidence in it.
+//
+// Among the lines fitting the template equally well, edits within long filler runs are preferred: OCR
+// engines often miscount padding, and seldom drop or add a filler next to other characters. The confidence
+// is the share of matching check digits, lowered by any strict violation and by the other candidates.
+fn best_repair(
+    idx: usize, line: &str, length: usize, template: &LineTemplate,
+) -> Option<(String, Vec<LengthEdit>, f64)> {
+    let chars: Vec<char> = line.chars().collect();
+    let specs: Vec<FieldSpec> = template
+        .specs
+        .iter()
+        .filter(|spec| spec.line == idx)
+        .map(|spec| FieldSpec { line: 0, ..*spec })
+        .collect();
+
+    // Distinct repaired lines, each with the least penalised edits producing it
+    let mut candidates: HashMap<String, Candidate> = HashMap::new();
+    for positions in edit_positions(&chars, length) {
+        let (repaired, edits, penalty) = apply_edits(&chars, &positions, length > chars.len());
+        if candidates
+            .get(&repaired)
+            .is_some_and(|candidate| candidate.penalty <= penalty)
+        {
+            continue;
+        }
+        let (mismatches, checks) = check_digit_mismatches(idx, &repaired, template);
+        let fit = Fit {
+            violations: strict_violations(std::slice::from_ref(&repaired), &specs).len(),
+            mismatches,
+        };
+        candidates.insert(
+            repaired,
+            Candidate {
+                fit,
+                checks,
+                edits,
+                penalty,
+            },
+        );
+    }
+
+    let best_fit = candidates.values().map(|candidate| candidate.fit).min()?;
+    let weight = |candidate: &Candidate| 1.0 / (candidate.penalty as f64 + 1.0);
+    let total_weight: f64 = candidates
+        .values()
+        .filter(|candidate| candidate.fit == best_fit)
+        .map(weight)
+        .sum();
+    let (repaired, best) = candidates
+        .into_iter()
+        .filter(|(_, candidate)| candidate.fit == best_fit)
+        .min_by(|a, b| a.1.penalty.cmp(&b.1.penalty).then_with(|| a.0.cmp(&b.0)))?;
+
+    let matched = (best.checks - best.fit.mismatches) as f64;
+    let quality = (matched + 1.0) / (best.checks as f64 + 1.0) / (best.fit.violations as f64 + 1.0);
+    let confidence = quality * weight(&best) / total_weight;
+    Some((repaired, best.edits, confidence))
+}
+
+// Every set of positions at which to insert fillers (line too short) or remove them (line too long).
+// Insertion positions are indexes of the original characters to insert before, from 0 to the line length.
+fn edit_positions(chars: &[char], length: usize) -> Vec<Vec<usize>> {
+    let insert = length > chars.len();
+    let choices: Vec<usize> = if insert {
+        (0..=chars.len()).collect()
+    } else {
+        (0..chars.len()).filter(|&idx| chars[idx] == '<').collect()
+    };
+
+    let mut sets = vec![Vec::new()];
+    for _ in 0..chars.len().abs_diff(length) {
+        let mut next = Vec::new();
+        for set in &sets {
+            // Several fillers may be inserted at the same position, but a character is only removed once
+            let from = match set.last() {
+                None => 0,
+                Some(&last) if insert => choices.partition_point(|&choice| choice < last),
+                Some(&last) => choices.partition_point(|&choice| choice <= last),
+            };
+            for &choice in &choices[from..] {
+                let mut extended = set.clone();
+                extended.push(choice);
+                next.push(extended);
+            }
+        }
+        sets = next;
+    }
+    sets
+}
+
+// Number of fillers around a position: the run it is part of, or the runs on both sides of an insertion point
+fn filler_run(chars: &[char], idx: usize) -> usize {
+    let before = chars[..idx]
+        .iter()
+        .rev()
+        .take_while(|&&ch| ch == '<')
+        .count();
+    let after = chars[idx..].iter().take_while(|&&ch| ch == '<').count();
+    before + after
+}
+
+// Applies the insertions or removals, returning the line, the edits and their penalty: none within a run of
+// three fillers or more, 1 within a shorter run and 2 away from any filler
+fn apply_edits(chars: &[char], positions: &[usize], insert: bool) -> (String, Vec<LengthEdit>, usize) {
+    let mut repaired = String::new();
+    let mut edits = Vec::new();
+    let mut penalty = 0;
+
+    for idx in 0..=chars.len() {
+        let edited = positions
+            .iter()
+            .filter(|&&position| position == idx)
+            .count();
+        if edited > 0 {
+            penalty += edited
+                * match filler_run(chars, idx) {
+                    0 => 2,
+                    1 | 2 => 1,
+                    _ => 0,
+                };
+        }
+
+        if insert {
+            for _ in 0..edited {
+                edits.push(LengthEdit::Inserted { column: repaired.len() });
+                repaired.push('<');
+            }
+        } else if edited > 0 {
+            edits.push(LengthEdit::Removed { column: idx });
+            continue;
+        }
+        if let Some(&ch) = chars.get(idx) {
+            repaired.push(ch);
+        }
+    }
+
+    (repaired, edits, penalty)
+}
+
+// Number of check digits of the template on this line that do not match, and the number checked
+fn check_digit_mismatches(idx: usize, line: &str, template: &LineTemplate) -> (usize, usize) {
+    let mut checks = 0;
+    let mut mismatches = 0;
+
+    for span in template.spans.iter().filter(|span| span.line == idx) {
+        let end = span.start + span.length;
+        checks += 1;
+        if !is_value_valid(&line[span.start..end], &line[end..=end]) {
+            mismatches += 1;
+        }
+    }
+
+    // The composite check digit is only usable when it covers this line alone
+    if let Some(composite) = template
+        .composite
+        .filter(|composite| composite.line == idx && composite.segments.iter().all(|&(line, _, _)| line == idx))
+    {
+        let covered: String = composite
+            .segments
+            .iter()
+            .map(|&(_, start, end)| &line[start..end])
+            .collect();
+        checks += 1;
+        if calculate_check_digits(&covered).map_or(true, |digit| digit != line[composite.position..=composite.position])
+        {
+            mismatches += 1;
+        }
+    }
+
+    (mismatches, checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field_formatter::FieldType;
+
+    const TD3_SPECS: [FieldSpec; 3] = [
+        FieldSpec::new(FieldType::Names, 0, 5, 39, false),
+        FieldSpec::new(FieldType::Nationality, 1, 10, 3, false),
+        FieldSpec::new(FieldType::Sex, 1, 20, 1, false),
+    ];
+    const TD3_SPANS: [CheckedSpan; 2] = [
+        CheckedSpan {
+            line: 1,
+            start: 0,
+            length: 9,
+            numeric: false,
+            overflow: 0,
+        },
+        CheckedSpan {
+            line: 1,
+            start: 28,
+            length: 14,
+            numeric: false,
+            overflow: 0,
+        },
+    ];
+    const TEMPLATE: LineTemplate = LineTemplate {
+        specs: &TD3_SPECS,
+        spans: &TD3_SPANS,
+        composite: None,
+    };
+
+    fn repair(lines: &[&str]) -> Result<(Vec<String>, Vec<LineRepair>), MrzError> {
+        let options = ParserOptions {
+            repair_line_length: true,
+            ..ParserOptions::default()
+        };
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        repair_lines(&options, &lines, 2, 44, &TEMPLATE)
+    }
+
+    #[test]
+    fn test_repair_dropped_filler() {
+        // A filler dropped from the personal number padding: the check digits realign once it is restored
+        let (lines, repairs) =
+            repair(&["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<10"])
+                .unwrap();
+        assert_eq!(lines[1], "L898902C36UTO7408122F1204159ZE184226B<<<<<10");
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(repairs[0].line, 1);
+        assert_eq!(repairs[0].edits, vec![LengthEdit::Inserted { column: 37 }]);
+        // `ZE18<4226B<<<<` happens to have the same check digit, which lowers the confidence
+        assert_eq!(repairs[0].confidence, 0.75);
+    }
+
+    #[test]
+    fn test_repair_duplicated_fillers() {
+        let (lines, repairs) =
+            repair(&["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<<<10"])
+                .unwrap();
+        assert_eq!(lines[1], "L898902C36UTO7408122F1204159ZE184226B<<<<<10");
+        assert_eq!(repairs[0].original.len(), 46);
+        assert_eq!(repairs[0].edits, vec![LengthEdit::Removed { column: 37 }, LengthEdit::Removed { column: 38 }]);
+        assert_eq!(repairs[0].confidence, 1.0);
+    }
+
+    #[test]
+    fn test_repair_without_check_digits() {
+        // Nothing pins the names line: the padding is shortened, with a lower confidence
+        let (lines, repairs) =
+            repair(&["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<10"])
+                .unwrap();
+        assert_eq!(lines[0], "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<");
+        assert!(repairs[0].confidence > 0.0 && repairs[0].confidence < 1.0);
+    }
+
+    #[test]
+    fn test_no_repair() {
+        let lines: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F1204159ZE184226B<<<<10".to_string(),
+        ];
+        assert_eq!(
+            repair_lines(&ParserOptions::default(), &lines, 2, 44, &TEMPLATE).unwrap_err(),
+            MrzError::InvalidLineLength {
+                line: 1,
+                expected: 44,
+                actual: 43
+            }
+        );
+
+        // Lines more than two characters off are left as they are
+        let lines = ["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<10"];
+        assert_eq!(
+            repair(&lines).unwrap_err(),
+            MrzError::InvalidLineLength {
+                line: 0,
+                expected: 44,
+                actual: 41
+            }
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::error::mrz_error::MrzError;
+use std::fmt;
+
+// Sex of the holder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Sex {
+    // Written as `M`.
+    Male,
+    // Written as `F`.
+    Female,
+    // Written as `X`, or as a filler by documents issued before `X` was introduced.
+    Unspecified,
+}
+
+impl Sex {
+    // from_mrz: Reads the sex character of an MRZ, returning None for a character that is not a sex.
+    pub fn from_mrz(ch: char) -> Option<Self> {
+        match ch {
+            'M' => Some(Sex::Male),
+            'F' => Some(Sex::Female),
+            'X' | '<' => Some(Sex::Unspecified),
+            _ => None,
+        }
+    }
+
+    // to_mrz: Returns the character written in the MRZ for the sex.
+    pub fn to_mrz(self) -> char {
+        match self {
+            Sex::Male => 'M',
+            Sex::Female => 'F',
+            Sex::Unspecified => 'X',
+        }
+    }
+
+    // parse: Reads a sex field, failing unless it holds exactly one sex character.
+    pub(crate) fn parse(from: &str) -> Result<Self, MrzError> {
+        let mut chars = from.chars();
+        match (chars.next().and_then(Sex::from_mrz), chars.next()) {
+            (Some(sex), None) => Ok(sex),
+            _ => Err(MrzError::InvalidSex(from.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Sex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Sex::Male => "MALE",
+            Sex::Female => "FEMALE",
+            Sex::Unspecified => "UNSPECIFIED",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sex() {
+        assert_eq!(Sex::parse("M"), Ok(Sex::Male));
+        assert_eq!(Sex::parse("F"), Ok(Sex::Female));
+        assert_eq!(Sex::parse("X"), Ok(Sex::Unspecified));
+        assert_eq!(Sex::parse("<"), Ok(Sex::Unspecified));
+        assert_eq!(Sex::parse("K"), Err(MrzError::InvalidSex("K".to_string())));
+        assert_eq!(Sex::Unspecified.to_string(), "UNSPECIFIED");
+        assert_eq!(Sex::Unspecified.to_mrz(), 'X');
+    }
+}
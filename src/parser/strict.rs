@@ -0,0 +1,259 @@
+use crate::error::mrz_error::MrzError;
+use crate::parser::field_formatter::FieldType;
+use std::fmt;
+
+// What is wrong with a character rejected by strict validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    // The character is not part of the ICAO 9303 character set (A-Z, 0-9 and `<`).
+    InvalidCharacter(char),
+    // The character is valid in an MRZ but not in this field, e.g. a digit in a name or a letter in a date.
+    UnexpectedCharacter(char),
+    // A filler splits a numeric value, e.g. `74<812`. Only whole `<<` parts of a date may be unknown.
+    EmbeddedFiller,
+    // A filler is out of place: a name starting with a filler or continuing after its padding, or a
+    // filler check digit following a non-empty value.
+    MisplacedFiller,
+}
+
+// A strict validation failure, at a zero-based line and column of the MRZ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ViolationKind::InvalidCharacter(ch) => format!("invalid character {:?}", ch),
+            ViolationKind::UnexpectedCharacter(ch) => format!("unexpected character {:?}", ch),
+            ViolationKind::EmbeddedFiller => "embedded filler".to_string(),
+            ViolationKind::MisplacedFiller => "misplaced filler".to_string(),
+        };
+        write!(f, "{} at line {}, column {}", description, self.line, self.column)
+    }
+}
+
+// Position of a field in the MRZ lines, used to check it against the rules of its type
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FieldSpec {
+    pub field_type: FieldType,
+    pub line: usize,
+    pub start: usize,
+    pub length: usize,
+    pub check_digit: bool,
+}
+
+impl FieldSpec {
+    pub const fn new(field_type: FieldType, line: usize, start: usize, length: usize, check_digit: bool) -> Self {
+        FieldSpec {
+            field_type,
+            line,
+            start,
+            length,
+            check_digit,
+        }
+    }
+}
+
+fn is_mrz_character(ch: char) -> bool {
+    ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '<'
+}
+
+// strict_violations: Checks every line against the ICAO 9303 character set and every field against the
+// characters and filler placement allowed for its type.
+pub(crate) fn strict_violations(lines: &[String], specs: &[FieldSpec]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        for (column, ch) in line.chars().enumerate() {
+            if !is_mrz_character(ch) {
+                violations.push(Violation {
+                    line: line_idx,
+                    column,
+                    kind: ViolationKind::InvalidCharacter(ch),
+                });
+            }
+        }
+    }
+
+    for spec in specs {
+        let Some(line) = lines.get(spec.line) else {
+            continue;
+        };
+        let chars: Vec<char> = line.chars().collect();
+        if spec.start + spec.length + spec.check_digit as usize > chars.len() {
+            continue;
+        }
+
+        let value = &chars[spec.start..spec.start + spec.length];
+        let mut report = |offset: usize, kind: ViolationKind| {
+            violations.push(Violation {
+                line: spec.line,
+                column: spec.start + offset,
+                kind,
+            })
+        };
+
+        for (offset, violation) in field_violations(spec.field_type, value) {
+            report(offset, violation);
+        }
+
+        if spec.check_digit {
+            let check_digit = chars[spec.start + spec.length];
+            let empty = value.iter().all(|&ch| ch == '<');
+            match check_digit {
+                '<' if !empty => report(spec.length, ViolationKind::MisplacedFiller),
+                ch if is_mrz_character(ch) && ch != '<' && !ch.is_ascii_digit() => {
+                    report(spec.length, ViolationKind::UnexpectedCharacter(ch))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    violations.sort_by_key(|violation| (violation.line, violation.column));
+    violations
+}
+
+// check_strict: Fails with every strict validation violation found in the lines, if any.
+pub(crate) fn check_strict(lines: &[String], specs: &[FieldSpec]) -> Result<(), MrzError> {
+    let violations = strict_violations(lines, specs);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(MrzError::StrictValidation(violations))
+    }
+}
+
+// is_allowed: Returns whether the character may appear in a field of the given type, whatever its position.
+pub(crate) fn is_allowed(field_type: FieldType, ch: char) -> bool {
+    match field_type {
+        FieldType::Birthdate | FieldType::ExpiryDate | FieldType::IssueDate => ch.is_ascii_digit() || ch == '<',
+        FieldType::Hash => ch.is_ascii_digit(),
+        FieldType::Sex => matches!(ch, 'M' | 'F' | 'X' | '<'),
+        FieldType::DocumentType | FieldType::CountryCode | FieldType::Nationality | FieldType::Names => {
+            ch.is_ascii_uppercase() || ch == '<'
+        }
+        FieldType::DocumentNumber | FieldType::PersonalNumber | FieldType::OptionalData => is_mrz_character(ch),
+    }
+}
+
+// Violations within a field value, as (offset, kind). Characters outside of the MRZ character set are
+// reported once at line level and skipped here.
+pub(crate) fn field_violations(field_type: FieldType, value: &[char]) -> Vec<(usize, ViolationKind)> {
+    let mut violations = Vec::new();
+    let unexpected = |ch: char| is_mrz_character(ch) && !is_allowed(field_type, ch);
+
+    match field_type {
+        FieldType::Birthdate | FieldType::ExpiryDate | FieldType::IssueDate => {
+            for (part_idx, part) in value.chunks(2).enumerate() {
+                let unknown = part.iter().all(|&ch| ch == '<');
+                for (idx, &ch) in part.iter().enumerate() {
+                    let offset = part_idx * 2 + idx;
+                    if ch == '<' && !unknown {
+                        violations.push((offset, ViolationKind::EmbeddedFiller));
+                    } else if unexpected(ch) {
+                        violations.push((offset, ViolationKind::UnexpectedCharacter(ch)));
+                    }
+                }
+            }
+        }
+        FieldType::Names => {
+            // Names are right-padded: no leading filler, and nothing after a run of three or more fillers
+            let mut filler_run = 0;
+            for (offset, &ch) in value.iter().enumerate() {
+                if ch == '<' {
+                    filler_run += 1;
+                    if offset == 0 {
+                        violations.push((offset, ViolationKind::MisplacedFiller));
+                    }
+                    continue;
+                }
+                if filler_run >= 3 {
+                    violations.push((offset, ViolationKind::MisplacedFiller));
+                }
+                filler_run = 0;
+                if unexpected(ch) {
+                    violations.push((offset, ViolationKind::UnexpectedCharacter(ch)));
+                }
+            }
+        }
+        _ => {
+            for (offset, &ch) in value.iter().enumerate() {
+                if unexpected(ch) {
+                    violations.push((offset, ViolationKind::UnexpectedCharacter(ch)));
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TD3_SPECS: [FieldSpec; 4] = [
+        FieldSpec::new(FieldType::Names, 0, 5, 39, false),
+        FieldSpec::new(FieldType::DocumentNumber, 1, 0, 9, true),
+        FieldSpec::new(FieldType::Birthdate, 1, 13, 6, true),
+        FieldSpec::new(FieldType::Sex, 1, 20, 1, false),
+    ];
+
+    #[test]
+    fn test_valid_lines() {
+        let lines = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        assert!(strict_violations(&lines, &TD3_SPECS).is_empty());
+    }
+
+    #[test]
+    fn test_violations() {
+        let lines = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<X<<".to_string(),
+            "L898902C3<UTO74<8122Q1204159ze184226B<<<<<10".to_string(),
+        ];
+        let violations = strict_violations(&lines, &TD3_SPECS);
+        assert_eq!(
+            violations,
+            vec![
+                Violation {
+                    line: 0,
+                    column: 41,
+                    kind: ViolationKind::MisplacedFiller
+                },
+                Violation {
+                    line: 1,
+                    column: 9,
+                    kind: ViolationKind::MisplacedFiller
+                },
+                Violation {
+                    line: 1,
+                    column: 15,
+                    kind: ViolationKind::EmbeddedFiller
+                },
+                Violation {
+                    line: 1,
+                    column: 20,
+                    kind: ViolationKind::UnexpectedCharacter('Q')
+                },
+                Violation {
+                    line: 1,
+                    column: 28,
+                    kind: ViolationKind::InvalidCharacter('z')
+                },
+                Violation {
+                    line: 1,
+                    column: 29,
+                    kind: ViolationKind::InvalidCharacter('e')
+                },
+            ]
+        );
+    }
+}
@@ -1,29 +1,103 @@
-use crate::constants::mrz_field_name::{
-    BIRTHDATE_FIELD, COUNTRY_CODE_FIELD, DOCUMENT_NUMBER_FIELD, DOCUMENT_TYPE_FIELD, EXPIRY_DATE_FIELD,
-    FINAL_CHECK_DIGIT_FIELD, NAME_FIELD, NATIONALITY_FIELD, OPTIONAL_DATA_1_FIELD, OPTIONAL_DATA_2_FIELD, SEX_FIELD,
-};
-use crate::constants::mrz_utils::{ISSUING_COUNTRY_CODES, TYPE1_NUMBER_OF_CHARACTERS_PER_LINE};
+use crate::constants::mrz_utils::TYPE1_NUMBER_OF_CHARACTERS_PER_LINE;
+use crate::error::mrz_error::MrzError;
+use crate::parser::confidence::field_confidence;
+use crate::parser::correction::{guided_corrections, CheckedSpan, CompositeSpan};
+use crate::parser::document::{DocumentKind, MrzDocument, TD1Document};
 use crate::parser::field_formatter::FieldFormatter;
 use crate::parser::field_formatter::FieldType::{
     Birthdate, CountryCode, DocumentNumber, DocumentType, ExpiryDate, Hash, Names, Nationality, OptionalData, Sex,
 };
 use crate::parser::mrz_field::MrzField;
+use crate::parser::options::ParserOptions;
 use crate::parser::parser::{IMRZParser, MRZResult};
-use crate::utils::utils::calculate_check_digits;
-use std::collections::HashMap;
+use crate::parser::repair::{repair_lines, LineTemplate};
+use crate::parser::strict::{check_strict, FieldSpec};
+use crate::utils::utils::{calculate_check_digits, is_value_valid};
+
+// Check-digit protected fields, in composite order: document number, birth date, expiry date
+const CHECKED_SPANS: [CheckedSpan; 3] = [
+    CheckedSpan {
+        line: 0,
+        start: 5,
+        length: 9,
+        numeric: false,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 0,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 8,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+];
+
+const COMPOSITE_SPAN: CompositeSpan = CompositeSpan {
+    segments: &[(0, 5, 30), (1, 0, 7), (1, 8, 15), (1, 18, 29)],
+    line: 1,
+    position: 29,
+};
+
+// Fields checked in strict mode
+const FIELD_SPECS: [FieldSpec; 11] = [
+    FieldSpec::new(DocumentType, 0, 0, 2, false),
+    FieldSpec::new(CountryCode, 0, 2, 3, false),
+    FieldSpec::new(DocumentNumber, 0, 5, 9, true),
+    FieldSpec::new(OptionalData, 0, 15, 15, false),
+    FieldSpec::new(Birthdate, 1, 0, 6, true),
+    FieldSpec::new(Sex, 1, 7, 1, false),
+    FieldSpec::new(ExpiryDate, 1, 8, 6, true),
+    FieldSpec::new(Nationality, 1, 15, 3, false),
+    FieldSpec::new(OptionalData, 1, 18, 11, false),
+    FieldSpec::new(Hash, 1, 29, 1, false),
+    FieldSpec::new(Names, 2, 0, 30, false),
+];
+
+// Layout of the checked fields, used to repair line lengths and to decode OCR alternatives
+pub(crate) const TEMPLATE: LineTemplate = LineTemplate {
+    specs: &FIELD_SPECS,
+    spans: &CHECKED_SPANS,
+    composite: Some(&COMPOSITE_SPAN),
+};
 
 #[derive(Default, Debug, Clone)]
-pub struct TD1 {}
+pub struct TD1 {
+    options: ParserOptions,
+}
 
 impl TD1 {
     pub fn new() -> Self {
-        TD1 {}
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        TD1 { options }
     }
 
     pub fn validate_all_check_digits(
         &self, document_number: &MrzField, optional_data1: &MrzField, birthdate: &MrzField, expiry_date: &MrzField,
         optional_data2: &MrzField, final_check_digit: &MrzField,
-    ) -> Result<bool, &'static str> {
+    ) -> Result<bool, MrzError> {
+        Ok(document_number.is_valid
+            && birthdate.is_valid
+            && expiry_date.is_valid
+            && self.validate_composite_check_digit(
+                document_number, optional_data1, birthdate, expiry_date, optional_data2, final_check_digit,
+            )?)
+    }
+
+    // The composite check digit covers the fields as laid out in the MRZ
+    fn validate_composite_check_digit(
+        &self, document_number: &MrzField, optional_data1: &MrzField, birthdate: &MrzField, expiry_date: &MrzField,
+        optional_data2: &MrzField, final_check_digit: &MrzField,
+    ) -> Result<bool, MrzError> {
         let composite_str = format!(
             "{}{}{}{}{}{}{}{}{}{}",
             document_number.raw_value,
@@ -39,42 +113,121 @@ impl TD1 {
         );
 
         let calculated_check_digit = calculate_check_digits(&composite_str)?;
+        Ok(calculated_check_digit == final_check_digit.raw_value)
+    }
 
-        Ok(document_number.is_valid
-            && birthdate.is_valid
-            && expiry_date.is_valid
-            && (calculated_check_digit == final_check_digit.value))
+    // Document numbers longer than 9 characters have `<` in place of their check digit and continue in the
+    // optional data, followed by their check digit and a filler. Returns the reassembled document number and
+    // the optional data left after it.
+    fn long_document_number(
+        &self, document_number: &MrzField, optional_data1: &MrzField,
+    ) -> Option<(MrzField, MrzField)> {
+        let raw = &optional_data1.raw_value;
+        let overflow_length = overflow_length(&document_number.check_digit, raw)?;
+        let end = overflow_length + 1;
+        let number_length = document_number.raw_value.len() + overflow_length;
+        let rest_start = (end + 1).min(raw.len());
+
+        let mut number = MrzField::new(
+            format!("{}{}", document_number.value, &raw[..overflow_length]),
+            format!("{}{}", document_number.raw_value, &raw[..overflow_length]),
+            raw[overflow_length..end].to_string(),
+            false,
+        );
+        number.is_valid = is_value_valid(&number.raw_value, &number.check_digit);
+        number.corrections = document_number
+            .corrections
+            .iter()
+            .filter(|substitution| substitution.position < document_number.raw_value.len())
+            .copied()
+            .collect();
+
+        let mut rest = MrzField::new(raw[rest_start..].to_string(), raw[rest_start..].to_string(), String::new(), true);
+
+        // Substitutions in the optional data move to the field they now belong to
+        for substitution in &optional_data1.corrections {
+            let mut moved = *substitution;
+            if substitution.position < overflow_length {
+                moved.position += document_number.raw_value.len();
+                number.corrections.push(moved);
+            } else if substitution.position == overflow_length {
+                moved.position = number_length;
+                number.corrections.push(moved);
+            } else if substitution.position >= rest_start {
+                moved.position -= rest_start;
+                rest.corrections.push(moved);
+            }
+        }
+
+        number.confidence = field_confidence(DocumentNumber, &number);
+        rest.confidence = field_confidence(OptionalData, &rest);
+
+        Some((number, rest))
+    }
+}
+
+// checked_spans: Returns the check-digit protected fields of a TD1 MRZ, the document number continuing in the
+// optional data of the first line when it is too long for its field.
+pub(crate) fn checked_spans(first_line: &str) -> [CheckedSpan; 3] {
+    let mut spans = CHECKED_SPANS;
+    let filler = spans[0].start + spans[0].length;
+    let overflow = first_line
+        .get(filler..filler + 1)
+        .zip(first_line.get(filler + 1..))
+        .and_then(|(check_digit, optional_data)| overflow_length(check_digit, optional_data));
+    if let Some(overflow) = overflow {
+        spans[0].overflow = overflow;
+    }
+    spans
+}
+
+// overflow_length: Returns the number of document number characters at the start of the first line optional
+// data, before the check digit of a long document number. None if the document number fits its field.
+pub(crate) fn overflow_length(check_digit: &str, optional_data: &str) -> Option<usize> {
+    if check_digit != "<" {
+        return None;
+    }
+
+    // The overflow needs at least one character before its check digit
+    let end = optional_data.find('<').unwrap_or(optional_data.len());
+    if end < 2 {
+        return None;
     }
+
+    Some(end - 1)
 }
 
 impl IMRZParser for TD1 {
-    fn parse(&self, input: &Vec<String>) -> Result<MRZResult, &'static str> {
-        if input.len() != 3 {
-            return Err("invalid mrz length");
-        }
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        let (mut lines, repairs) =
+            repair_lines(&self.options, input, 3, TYPE1_NUMBER_OF_CHARACTERS_PER_LINE, &TEMPLATE)?;
 
-        for line in input {
-            if line.len() != TYPE1_NUMBER_OF_CHARACTERS_PER_LINE {
-                return Err("invalid mrz type 1 line length");
-            }
+        if self.options.strict {
+            // A `<` in place of the document number check digit marks a long document number
+            let mut specs = FIELD_SPECS;
+            specs[2].check_digit = !lines[0][14..].starts_with('<');
+            check_strict(&lines, &specs)?;
         }
 
-        let first_line = &input[0];
-        let second_line = &input[1];
-        let third_line = &input[2];
+        let spans = checked_spans(&lines[0]);
+        let mut guided = guided_corrections(&self.options, &mut lines, &spans, Some(&COMPOSITE_SPAN));
+
+        let first_line = &lines[0];
+        let second_line = &lines[1];
+        let third_line = &lines[2];
 
-        let formatter = FieldFormatter::new(true);
+        let formatter = FieldFormatter::new(self.options.correction, self.options.dates);
 
         // Parse first line
         let document_type = formatter.field(DocumentType, first_line, 0, 2, false)?;
         let country_code = formatter.field(CountryCode, first_line, 2, 3, false)?;
-        let document_number = formatter.field(DocumentNumber, first_line, 5, 9, true)?;
-        let optional_data1 = formatter.field(OptionalData, first_line, 15, 15, false)?;
+        let mut document_number = formatter.field(DocumentNumber, first_line, 5, 9, true)?;
+        let mut optional_data1 = formatter.field(OptionalData, first_line, 15, 15, false)?;
 
         // Parse second line
-        let birthdate = formatter.field(Birthdate, second_line, 0, 6, true)?;
+        let mut birthdate = formatter.field(Birthdate, second_line, 0, 6, true)?;
         let sex = formatter.field(Sex, second_line, 7, 1, false)?;
-        let expiry_date = formatter.field(ExpiryDate, second_line, 8, 6, true)?;
+        let mut expiry_date = formatter.field(ExpiryDate, second_line, 8, 6, true)?;
         let nationality = formatter.field(Nationality, second_line, 15, 3, false)?;
         let optional_data2 = formatter.field(OptionalData, second_line, 18, 11, false)?;
         let final_check_digit = formatter.field(Hash, second_line, 29, 1, false)?;
@@ -82,41 +235,64 @@ impl IMRZParser for TD1 {
         // Parse third line
         let name = formatter.field(Names, third_line, 0, 30, false)?;
 
+        // Substitutions in the overflow of a long document number are read in the optional data first
+        let (mut number, mut overflow): (Vec<_>, Vec<_>) = guided[0]
+            .drain(..)
+            .partition(|substitution| spans[0].overflow == 0 || substitution.position < spans[0].length);
+        for substitution in &mut overflow {
+            substitution.position -= spans[0].length;
+        }
+        document_number.append_corrections(&mut number);
+        optional_data1.append_corrections(&mut overflow);
+        birthdate.append_corrections(&mut guided[1]);
+        expiry_date.append_corrections(&mut guided[2]);
+
         // Validate all check digits
-        let is_valid = self.validate_all_check_digits(
-            &document_number, &optional_data1, &birthdate, &expiry_date, &optional_data2, &final_check_digit,
-        )?;
-
-        let mut parsed_result: HashMap<String, MrzField> = HashMap::new();
-        parsed_result.insert(NAME_FIELD.to_string(), name);
-        parsed_result.insert(DOCUMENT_TYPE_FIELD.to_string(), document_type);
-        parsed_result.insert(COUNTRY_CODE_FIELD.to_string(), country_code);
-        parsed_result.insert(DOCUMENT_NUMBER_FIELD.to_string(), document_number);
-        parsed_result.insert(OPTIONAL_DATA_1_FIELD.to_string(), optional_data1);
-        parsed_result.insert(BIRTHDATE_FIELD.to_string(), birthdate);
-        parsed_result.insert(SEX_FIELD.to_string(), sex);
-        parsed_result.insert(EXPIRY_DATE_FIELD.to_string(), expiry_date);
-        parsed_result.insert(NATIONALITY_FIELD.to_string(), nationality);
-        parsed_result.insert(OPTIONAL_DATA_2_FIELD.to_string(), optional_data2);
-        parsed_result.insert(FINAL_CHECK_DIGIT_FIELD.to_string(), final_check_digit);
-
-        let issuing_state = ISSUING_COUNTRY_CODES
-            .get(&parsed_result[COUNTRY_CODE_FIELD].value as &str)
-            .unwrap_or(&"Unknown")
-            .to_string();
-
-        Ok(MRZResult {
-            is_visa: false,
-            is_valid,
-            fields: parsed_result,
-            issuing_state,
-        })
+        let is_valid = match self.long_document_number(&document_number, &optional_data1) {
+            None => self.validate_all_check_digits(
+                &document_number, &optional_data1, &birthdate, &expiry_date, &optional_data2, &final_check_digit,
+            )?,
+            Some((long_number, rest)) => {
+                let is_valid = long_number.is_valid
+                    && birthdate.is_valid
+                    && expiry_date.is_valid
+                    && self.validate_composite_check_digit(
+                        &document_number, &optional_data1, &birthdate, &expiry_date, &optional_data2,
+                        &final_check_digit,
+                    )?;
+                document_number = long_number;
+                optional_data1 = rest;
+                is_valid
+            }
+        };
+
+        let kind = DocumentKind::from_document_type(&document_type.value);
+
+        let document = MrzDocument::TD1(TD1Document {
+            document_type,
+            issuing_country: country_code,
+            document_number,
+            optional_data_1: optional_data1,
+            birth_date: birthdate,
+            sex,
+            expiry_date,
+            nationality,
+            optional_data_2: optional_data2,
+            composite_check_digit: final_check_digit,
+            names: name,
+        });
+
+        MRZResult::new(&self.options, document, kind, is_valid, repairs)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::mrz_field_name::{DOCUMENT_NUMBER_FIELD, SEX_FIELD};
+    use crate::parser::correction::Substitution;
+    use crate::parser::options::CorrectionMode;
+    use crate::parser::sex::Sex;
 
     #[test]
     fn test_parse_td1() {
@@ -126,9 +302,97 @@ mod tests {
             "ERIKSSON<<ANNA<MARIA<<<<<<<<<<".to_string(),
         ];
 
-        let mut td1 = TD1::new();
+        let td1 = TD1::new();
         let result = td1.parse(&mrz_string).unwrap();
+        match &result.document {
+            MrzDocument::TD1(doc) => {
+                assert_eq!(doc.document_number.value, "D23145890");
+                assert!(doc.document_number.is_valid);
+                assert_eq!(doc.nationality.value, "UTO");
+                assert_eq!(doc.composite_check_digit.raw_value, "6");
+            }
+            _ => panic!("expected a TD1 document"),
+        }
+        assert_eq!(result.fields().len(), 11);
 
         println!("{:?}", result)
     }
+
+    // Long document number specimen from ICAO 9303 Part 5, Appendix B
+    #[test]
+    fn test_parse_td1_long_document_number() {
+        let mrz_string: Vec<String> = vec![
+            "I<UTOD23145890<7349<<<<<<<<<<<".to_string(),
+            "3407127M9507122UTO<<<<<<<<<<<2".to_string(),
+            "STEVENSON<<PETER<JOHN<<<<<<<<<".to_string(),
+        ];
+
+        let options = ParserOptions {
+            strict: true,
+            ..ParserOptions::default()
+        };
+        let result = TD1::with_options(options).parse(&mrz_string).unwrap();
+        assert!(result.is_valid);
+        match &result.document {
+            MrzDocument::TD1(doc) => {
+                assert_eq!(doc.document_number.value, "D23145890734");
+                assert_eq!(doc.document_number.check_digit, "9");
+                assert!(doc.document_number.is_valid);
+                assert_eq!(doc.optional_data_1.raw_value, "<<<<<<<<<<");
+            }
+            _ => panic!("expected a TD1 document"),
+        }
+        assert_eq!(result.mrz_information().unwrap(), "D23145890734934071279507122");
+
+        // A wrong check digit in the overflow invalidates the document number
+        let mut invalid = mrz_string.clone();
+        invalid[0].replace_range(18..19, "8");
+        let result = TD1::new().parse(&invalid).unwrap();
+        assert!(!result.get(DOCUMENT_NUMBER_FIELD).unwrap().is_valid);
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    fn test_parse_td1_long_document_number_guided() {
+        // The `7` of the overflow, in the optional data, was read as `T`
+        let mrz_string: Vec<String> = vec![
+            "I<UTOD23145890<T349<<<<<<<<<<<".to_string(),
+            "3407127M9507122UTO<<<<<<<<<<<2".to_string(),
+            "STEVENSON<<PETER<JOHN<<<<<<<<<".to_string(),
+        ];
+        let options = ParserOptions {
+            correction: CorrectionMode::CheckDigitGuided,
+            ..ParserOptions::default()
+        };
+        let result = TD1::with_options(options).parse(&mrz_string).unwrap();
+        assert!(result.is_valid);
+
+        let document_number = result.get(DOCUMENT_NUMBER_FIELD).unwrap();
+        assert_eq!(document_number.value, "D23145890734");
+        assert_eq!(
+            document_number.corrections,
+            vec![Substitution {
+                position: 9,
+                from: 'T',
+                to: '7'
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_td1_sex() {
+        let mut mrz_string: Vec<String> = vec![
+            "I<UTOD231458907<<<<<<<<<<<<<<<".to_string(),
+            "7408122X1204159UTO<<<<<<<<<<<6".to_string(),
+            "ERIKSSON<<ANNA<MARIA<<<<<<<<<<".to_string(),
+        ];
+        let result = TD1::new().parse(&mrz_string).unwrap();
+        let sex = result.get(SEX_FIELD).unwrap();
+        assert_eq!(sex.value, "UNSPECIFIED");
+        assert_eq!(sex.get_sex(), Some(Sex::Unspecified));
+        assert!(result.is_valid);
+
+        mrz_string[1].replace_range(7..8, "K");
+        assert_eq!(TD1::new().parse(&mrz_string).unwrap_err(), MrzError::InvalidSex("K".to_string()));
+    }
 }
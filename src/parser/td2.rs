@@ -1,73 +1,134 @@
-use crate::constants::mrz_field_name::{
-    BIRTHDATE_FIELD, COUNTRY_CODE_FIELD, DOCUMENT_NUMBER_FIELD, DOCUMENT_TYPE_FIELD, EXPIRY_DATE_FIELD,
-    FINAL_CHECK_DIGIT_FIELD, NAME_FIELD, NATIONALITY_FIELD, OPTIONAL_DATA_1_FIELD, SEX_FIELD,
-};
-use crate::constants::mrz_utils::{ISSUING_COUNTRY_CODES, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE};
+use crate::constants::mrz_utils::{FRENCH_ID_PREFIX, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE};
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{guided_corrections, CheckedSpan, CompositeSpan};
+use crate::parser::document::{DocumentKind, MrzDocument, TD2Document};
 use crate::parser::field_formatter::FieldFormatter;
 use crate::parser::field_formatter::FieldType::{
     Birthdate, CountryCode, DocumentNumber, DocumentType, ExpiryDate, Hash, Names, Nationality, PersonalNumber, Sex,
 };
+use crate::parser::french_id_card::FrenchIdCard;
+use crate::parser::mrv::MRVB;
 use crate::parser::mrz_field::MrzField;
+use crate::parser::options::ParserOptions;
 use crate::parser::parser::{IMRZParser, MRZResult};
+use crate::parser::repair::{repair_lines, LineTemplate};
+use crate::parser::strict::{check_strict, FieldSpec};
 use crate::utils::utils::calculate_check_digits;
-use std::collections::HashMap;
+
+// Check-digit protected fields, in composite order: document number, birth date, expiry date
+const CHECKED_SPANS: [CheckedSpan; 3] = [
+    CheckedSpan {
+        line: 1,
+        start: 0,
+        length: 9,
+        numeric: false,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 13,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 21,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+];
+
+const COMPOSITE_SPAN: CompositeSpan = CompositeSpan {
+    segments: &[(1, 0, 10), (1, 13, 20), (1, 21, 35)],
+    line: 1,
+    position: 35,
+};
+
+// Fields checked in strict mode
+const FIELD_SPECS: [FieldSpec; 9] = [
+    FieldSpec::new(DocumentType, 0, 0, 2, false),
+    FieldSpec::new(CountryCode, 0, 2, 3, false),
+    FieldSpec::new(Names, 0, 5, 31, false),
+    FieldSpec::new(DocumentNumber, 1, 0, 9, true),
+    FieldSpec::new(Nationality, 1, 10, 3, false),
+    FieldSpec::new(Birthdate, 1, 13, 6, true),
+    FieldSpec::new(Sex, 1, 20, 1, false),
+    FieldSpec::new(ExpiryDate, 1, 21, 6, true),
+    FieldSpec::new(Hash, 1, 35, 1, false),
+];
+
+// Layout of the checked fields, used to repair line lengths and to decode OCR alternatives
+pub(crate) const TEMPLATE: LineTemplate = LineTemplate {
+    specs: &FIELD_SPECS,
+    spans: &CHECKED_SPANS,
+    composite: Some(&COMPOSITE_SPAN),
+};
 
 #[derive(Default, Debug, Clone)]
-pub struct TD2 {}
+pub struct TD2 {
+    options: ParserOptions,
+}
 
 impl TD2 {
     pub fn new() -> Self {
-        TD2 {}
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        TD2 { options }
     }
 
     fn validate_all_check_digits(
         &self, document_number: &MrzField, birthdate: &MrzField, expiry_date: &MrzField, optional_data: &MrzField,
-        final_check_digit: Option<&MrzField>,
-    ) -> Result<bool, &'static str> {
-        if let Some(final_check) = final_check_digit {
-            let composite_str = format!(
-                "{}{}{}{}{}{}{}",
-                document_number.raw_value,
-                document_number.check_digit,
-                birthdate.raw_value,
-                birthdate.check_digit,
-                expiry_date.raw_value,
-                expiry_date.check_digit,
-                optional_data.raw_value
-            );
-
-            let calculated_check_digit = calculate_check_digits(&composite_str)?;
-            Ok(document_number.is_valid
-                && birthdate.is_valid
-                && expiry_date.is_valid
-                && calculated_check_digit == final_check.raw_value)
-        } else {
-            Ok(document_number.is_valid && birthdate.is_valid && expiry_date.is_valid)
-        }
+        final_check_digit: &MrzField,
+    ) -> Result<bool, MrzError> {
+        let composite_str = format!(
+            "{}{}{}{}{}{}{}",
+            document_number.raw_value,
+            document_number.check_digit,
+            birthdate.raw_value,
+            birthdate.check_digit,
+            expiry_date.raw_value,
+            expiry_date.check_digit,
+            optional_data.raw_value
+        );
+
+        let calculated_check_digit = calculate_check_digits(&composite_str)?;
+        Ok(document_number.is_valid
+            && birthdate.is_valid
+            && expiry_date.is_valid
+            && calculated_check_digit == final_check_digit.raw_value)
     }
 }
 
 impl IMRZParser for TD2 {
-    fn parse(&self, input: &Vec<String>) -> Result<MRZResult, &'static str> {
-        if input.len() != 2 {
-            return Err("invalid mrz length");
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        // Visas and French identity cards of the same size have their own layout
+        if input.first().is_some_and(|line| line.starts_with('V')) {
+            return MRVB::with_options(self.options.clone()).parse(input);
         }
-
-        for line in input {
-            if line.len() != TYPE2_NUMBER_OF_CHARACTERS_PER_LINE {
-                return Err("invalid mrz type 2 line length");
-            }
+        if input
+            .first()
+            .is_some_and(|line| line.starts_with(FRENCH_ID_PREFIX))
+        {
+            return FrenchIdCard::with_options(self.options.clone()).parse(input);
         }
 
-        let mut is_visa = false;
-        let first_line = &input[0];
-        let second_line = &input[1];
-        let formatter = FieldFormatter::new(true);
+        let (mut lines, repairs) =
+            repair_lines(&self.options, input, 2, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, &TEMPLATE)?;
 
-        if first_line.chars().nth(0) == Some('V') {
-            is_visa = true;
+        if self.options.strict {
+            check_strict(&lines, &FIELD_SPECS)?;
         }
 
+        let mut guided = guided_corrections(&self.options, &mut lines, &CHECKED_SPANS, Some(&COMPOSITE_SPAN));
+
+        let first_line = &lines[0];
+        let second_line = &lines[1];
+        let formatter = FieldFormatter::new(self.options.correction, self.options.dates);
+
         // Parse first line
         let document_type = formatter.field(DocumentType, first_line, 0, 2, false)?;
 
@@ -76,80 +137,51 @@ impl IMRZParser for TD2 {
         let name = formatter.field(Names, first_line, 5, 31, false)?;
 
         // Parse second line
-        let document_number = formatter.field(DocumentNumber, second_line, 0, 9, true)?;
+        let mut document_number = formatter.field(DocumentNumber, second_line, 0, 9, true)?;
 
         let nationality = formatter.field(Nationality, second_line, 10, 3, false)?;
 
-        let birthdate = formatter.field(Birthdate, second_line, 13, 6, true)?;
+        let mut birthdate = formatter.field(Birthdate, second_line, 13, 6, true)?;
 
         let sex = formatter.field(Sex, second_line, 20, 1, false)?;
 
-        let expiry_date = formatter.field(ExpiryDate, second_line, 21, 6, true)?;
-
-        let mut optional_data: MrzField = MrzField {
-            value: "".to_string(),
-            raw_value: "".to_string(),
-            check_digit: "".to_string(),
-            is_valid: true,
-        };
-
-        let mut final_check_digit: MrzField = MrzField {
-            value: "".to_string(),
-            raw_value: "".to_string(),
-            check_digit: "".to_string(),
-            is_valid: true,
-        };
-
-        let mut is_valid: bool = false;
-
-        if is_visa {
-            optional_data = formatter.field(PersonalNumber, second_line, 28, 8, false)?;
-
-            is_valid =
-                self.validate_all_check_digits(&document_number, &birthdate, &expiry_date, &optional_data, None)?;
-        } else {
-            optional_data = formatter.field(PersonalNumber, second_line, 28, 7, true)?;
-
-            final_check_digit = formatter.field(Hash, second_line, 35, 1, false)?;
-
-            is_valid = self.validate_all_check_digits(
-                &document_number,
-                &birthdate,
-                &expiry_date,
-                &optional_data,
-                Some(&final_check_digit),
-            )?;
-        }
+        let mut expiry_date = formatter.field(ExpiryDate, second_line, 21, 6, true)?;
+
+        document_number.append_corrections(&mut guided[0]);
+        birthdate.append_corrections(&mut guided[1]);
+        expiry_date.append_corrections(&mut guided[2]);
+
+        let optional_data = formatter.field(PersonalNumber, second_line, 28, 7, false)?;
+
+        let final_check_digit = formatter.field(Hash, second_line, 35, 1, false)?;
+
+        let is_valid = self.validate_all_check_digits(
+            &document_number, &birthdate, &expiry_date, &optional_data, &final_check_digit,
+        )?;
+
+        let kind = DocumentKind::from_document_type(&document_type.value);
+
+        let document = MrzDocument::TD2(TD2Document {
+            document_type,
+            issuing_country: country_code,
+            names: name,
+            document_number,
+            nationality,
+            birth_date: birthdate,
+            sex,
+            expiry_date,
+            optional_data_1: optional_data,
+            composite_check_digit: final_check_digit,
+        });
 
-        let mut parsed_result: HashMap<String, MrzField> = HashMap::new();
-        parsed_result.insert(NAME_FIELD.to_string(), name);
-        parsed_result.insert(DOCUMENT_TYPE_FIELD.to_string(), document_type);
-        parsed_result.insert(COUNTRY_CODE_FIELD.to_string(), country_code);
-        parsed_result.insert(DOCUMENT_NUMBER_FIELD.to_string(), document_number);
-        parsed_result.insert(NATIONALITY_FIELD.to_string(), nationality);
-        parsed_result.insert(BIRTHDATE_FIELD.to_string(), birthdate);
-        parsed_result.insert(SEX_FIELD.to_string(), sex);
-        parsed_result.insert(EXPIRY_DATE_FIELD.to_string(), expiry_date);
-        parsed_result.insert(OPTIONAL_DATA_1_FIELD.to_string(), optional_data);
-        parsed_result.insert(FINAL_CHECK_DIGIT_FIELD.to_string(), final_check_digit);
-
-        let issuing_state = ISSUING_COUNTRY_CODES
-            .get(&parsed_result[COUNTRY_CODE_FIELD].value as &str)
-            .unwrap_or(&"Unknown")
-            .to_string();
-
-        Ok(MRZResult {
-            is_visa,
-            is_valid,
-            fields: parsed_result,
-            issuing_state,
-        })
+        MRZResult::new(&self.options, document, kind, is_valid, repairs)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::mrz_field_name::{BIRTHDATE_FIELD, FINAL_CHECK_DIGIT_FIELD};
 
     #[test]
     fn test_parse_td2() {
@@ -158,10 +190,11 @@ mod tests {
             "D231458907UTO7408122F1204159<<<<<<<6".to_string(),
         ];
 
-        let mut td2 = TD2::new();
+        let td2 = TD2::new();
         let result = td2.parse(&mrz_string).unwrap();
-        assert_eq!(result.is_visa, false);
-        assert_eq!(result.is_valid, true);
+        assert!(!result.is_visa);
+        assert_eq!(result.kind, DocumentKind::IdCard);
+        assert!(result.is_valid);
         println!("{:?}", result)
     }
 
@@ -172,10 +205,13 @@ mod tests {
             "L8988901C4XXX4009078F9612109<<<<<<<<".to_string(),
         ];
 
-        let mut td2 = TD2::new();
+        let td2 = TD2::new();
         let result = td2.parse(&mrz_string).unwrap();
-        assert_eq!(result.is_visa, true);
-        assert_eq!(result.is_valid, true);
+        assert!(result.is_visa);
+        assert!(result.is_valid);
+        assert!(matches!(result.document, MrzDocument::MRVB(_)));
+        assert!(result.get(FINAL_CHECK_DIGIT_FIELD).is_none());
+        assert_eq!(result.get(BIRTHDATE_FIELD).unwrap().value, "400907");
         println!("{:?}", result)
     }
 }
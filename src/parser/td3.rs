@@ -1,74 +1,136 @@
-use crate::constants::mrz_field_name::{
-    BIRTHDATE_FIELD, COUNTRY_CODE_FIELD, DOCUMENT_NUMBER_FIELD, DOCUMENT_TYPE_FIELD, EXPIRY_DATE_FIELD,
-    FINAL_CHECK_DIGIT_FIELD, NAME_FIELD, NATIONALITY_FIELD, OPTIONAL_DATA_1_FIELD, SEX_FIELD,
-};
-use crate::constants::mrz_utils::{ISSUING_COUNTRY_CODES, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE};
+use crate::constants::mrz_utils::TYPE3_NUMBER_OF_CHARACTERS_PER_LINE;
+use crate::error::mrz_error::MrzError;
+use crate::parser::correction::{guided_corrections, CheckedSpan, CompositeSpan};
+use crate::parser::document::{DocumentKind, MrzDocument, TD3Document};
 use crate::parser::field_formatter::FieldFormatter;
 use crate::parser::field_formatter::FieldType::{
     Birthdate, CountryCode, DocumentNumber, DocumentType, ExpiryDate, Hash, Names, Nationality, PersonalNumber, Sex,
 };
+use crate::parser::mrv::MRVA;
 use crate::parser::mrz_field::MrzField;
+use crate::parser::options::ParserOptions;
 use crate::parser::parser::{IMRZParser, MRZResult};
+use crate::parser::repair::{repair_lines, LineTemplate};
+use crate::parser::strict::{check_strict, FieldSpec};
 use crate::utils::utils::calculate_check_digits;
-use std::collections::HashMap;
+
+// Check-digit protected fields, in composite order: document number, birth date, expiry date, personal number
+const CHECKED_SPANS: [CheckedSpan; 4] = [
+    CheckedSpan {
+        line: 1,
+        start: 0,
+        length: 9,
+        numeric: false,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 13,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 21,
+        length: 6,
+        numeric: true,
+        overflow: 0,
+    },
+    CheckedSpan {
+        line: 1,
+        start: 28,
+        length: 14,
+        numeric: false,
+        overflow: 0,
+    },
+];
+
+const COMPOSITE_SPAN: CompositeSpan = CompositeSpan {
+    segments: &[(1, 0, 10), (1, 13, 20), (1, 21, 43)],
+    line: 1,
+    position: 43,
+};
+
+// Fields checked in strict mode
+const FIELD_SPECS: [FieldSpec; 10] = [
+    FieldSpec::new(DocumentType, 0, 0, 2, false),
+    FieldSpec::new(CountryCode, 0, 2, 3, false),
+    FieldSpec::new(Names, 0, 5, 39, false),
+    FieldSpec::new(DocumentNumber, 1, 0, 9, true),
+    FieldSpec::new(Nationality, 1, 10, 3, false),
+    FieldSpec::new(Birthdate, 1, 13, 6, true),
+    FieldSpec::new(Sex, 1, 20, 1, false),
+    FieldSpec::new(ExpiryDate, 1, 21, 6, true),
+    FieldSpec::new(PersonalNumber, 1, 28, 14, true),
+    FieldSpec::new(Hash, 1, 43, 1, false),
+];
+
+// Layout of the checked fields, used to repair line lengths and to decode OCR alternatives
+pub(crate) const TEMPLATE: LineTemplate = LineTemplate {
+    specs: &FIELD_SPECS,
+    spans: &CHECKED_SPANS,
+    composite: Some(&COMPOSITE_SPAN),
+};
 
 #[derive(Default, Debug, Clone)]
-pub struct TD3 {}
+pub struct TD3 {
+    options: ParserOptions,
+}
 
 impl TD3 {
     pub fn new() -> Self {
-        TD3 {}
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        TD3 { options }
     }
 
     fn validate_all_check_digits(
         &self, document_number: &MrzField, birthdate: &MrzField, expiry_date: &MrzField, personal_number: &MrzField,
-        final_check_digit: Option<&MrzField>,
-    ) -> Result<bool, &'static str> {
-        if let Some(final_check) = final_check_digit {
-            let composite_str = format!(
-                "{}{}{}{}{}{}{}{}",
-                document_number.raw_value,
-                document_number.check_digit,
-                birthdate.raw_value,
-                birthdate.check_digit,
-                expiry_date.raw_value,
-                expiry_date.check_digit,
-                personal_number.raw_value,
-                personal_number.check_digit,
-            );
-
-            let calculated_check_digit = calculate_check_digits(&composite_str)?;
-            Ok(document_number.is_valid
-                && birthdate.is_valid
-                && expiry_date.is_valid
-                && personal_number.is_valid
-                && calculated_check_digit == final_check.raw_value)
-        } else {
-            Ok(document_number.is_valid && birthdate.is_valid && expiry_date.is_valid)
-        }
+        final_check_digit: &MrzField,
+    ) -> Result<bool, MrzError> {
+        let composite_str = format!(
+            "{}{}{}{}{}{}{}{}",
+            document_number.raw_value,
+            document_number.check_digit,
+            birthdate.raw_value,
+            birthdate.check_digit,
+            expiry_date.raw_value,
+            expiry_date.check_digit,
+            personal_number.raw_value,
+            personal_number.check_digit,
+        );
+
+        let calculated_check_digit = calculate_check_digits(&composite_str)?;
+        Ok(document_number.is_valid
+            && birthdate.is_valid
+            && expiry_date.is_valid
+            && personal_number.is_valid
+            && calculated_check_digit == final_check_digit.raw_value)
     }
 }
 
 impl IMRZParser for TD3 {
-    fn parse(&self, input: &Vec<String>) -> Result<MRZResult, &'static str> {
-        if input.len() != 2 {
-            return Err("invalid mrz length");
+    fn parse(&self, input: &[String]) -> Result<MRZResult, MrzError> {
+        // Visas of the same size have their own layout
+        if input.first().is_some_and(|line| line.starts_with('V')) {
+            return MRVA::with_options(self.options.clone()).parse(input);
         }
 
-        for line in input {
-            if line.len() != TYPE3_NUMBER_OF_CHARACTERS_PER_LINE {
-                return Err("invalid mrz type 2 line length");
-            }
+        let (mut lines, repairs) =
+            repair_lines(&self.options, input, 2, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE, &TEMPLATE)?;
+
+        if self.options.strict {
+            check_strict(&lines, &FIELD_SPECS)?;
         }
 
-        let mut is_visa = false;
-        let first_line = &input[0];
-        let second_line = &input[1];
-        let formatter = FieldFormatter::new(true);
+        let mut guided = guided_corrections(&self.options, &mut lines, &CHECKED_SPANS, Some(&COMPOSITE_SPAN));
 
-        if first_line.chars().nth(0) == Some('V') {
-            is_visa = true;
-        }
+        let first_line = &lines[0];
+        let second_line = &lines[1];
+        let formatter = FieldFormatter::new(self.options.correction, self.options.dates);
 
         // Parse first line
         let document_type = formatter.field(DocumentType, first_line, 0, 2, false)?;
@@ -78,80 +140,58 @@ impl IMRZParser for TD3 {
         let name = formatter.field(Names, first_line, 5, 39, false)?;
 
         // Parse second line
-        let document_number = formatter.field(DocumentNumber, second_line, 0, 9, true)?;
+        let mut document_number = formatter.field(DocumentNumber, second_line, 0, 9, true)?;
 
         let nationality = formatter.field(Nationality, second_line, 10, 3, false)?;
 
-        let birthdate = formatter.field(Birthdate, second_line, 13, 6, true)?;
+        let mut birthdate = formatter.field(Birthdate, second_line, 13, 6, true)?;
 
         let sex = formatter.field(Sex, second_line, 20, 1, false)?;
 
-        let expiry_date = formatter.field(ExpiryDate, second_line, 21, 6, true)?;
+        let mut expiry_date = formatter.field(ExpiryDate, second_line, 21, 6, true)?;
 
-        let mut optional_data: MrzField = MrzField {
-            value: "".to_string(),
-            raw_value: "".to_string(),
-            check_digit: "".to_string(),
-            is_valid: true,
-        };
-
-        let mut final_check_digit: MrzField = MrzField {
-            value: "".to_string(),
-            raw_value: "".to_string(),
-            check_digit: "".to_string(),
-            is_valid: true,
-        };
+        document_number.append_corrections(&mut guided[0]);
+        birthdate.append_corrections(&mut guided[1]);
+        expiry_date.append_corrections(&mut guided[2]);
 
-        let mut is_valid: bool = false;
+        let mut optional_data = formatter.field(PersonalNumber, second_line, 28, 14, true)?;
+        optional_data.append_corrections(&mut guided[3]);
 
-        if is_visa {
-            optional_data = formatter.field(PersonalNumber, second_line, 28, 16, false)?;
+        let final_check_digit = formatter.field(Hash, second_line, 43, 1, false)?;
 
-            is_valid =
-                self.validate_all_check_digits(&document_number, &birthdate, &expiry_date, &optional_data, None)?;
-        } else {
-            optional_data = formatter.field(PersonalNumber, second_line, 28, 14, true)?;
+        let is_valid = self.validate_all_check_digits(
+            &document_number, &birthdate, &expiry_date, &optional_data, &final_check_digit,
+        )?;
 
-            final_check_digit = formatter.field(Hash, second_line, 43, 1, false)?;
+        let kind = DocumentKind::from_document_type(&document_type.value);
 
-            is_valid = self.validate_all_check_digits(
-                &document_number,
-                &birthdate,
-                &expiry_date,
-                &optional_data,
-                Some(&final_check_digit),
-            )?;
-        }
+        let document = MrzDocument::TD3(TD3Document {
+            document_type,
+            issuing_country: country_code,
+            names: name,
+            document_number,
+            nationality,
+            birth_date: birthdate,
+            sex,
+            expiry_date,
+            optional_data_1: optional_data,
+            composite_check_digit: final_check_digit,
+        });
 
-        let mut parsed_result: HashMap<String, MrzField> = HashMap::new();
-        parsed_result.insert(NAME_FIELD.to_string(), name);
-        parsed_result.insert(DOCUMENT_TYPE_FIELD.to_string(), document_type);
-        parsed_result.insert(COUNTRY_CODE_FIELD.to_string(), country_code);
-        parsed_result.insert(DOCUMENT_NUMBER_FIELD.to_string(), document_number);
-        parsed_result.insert(NATIONALITY_FIELD.to_string(), nationality);
-        parsed_result.insert(BIRTHDATE_FIELD.to_string(), birthdate);
-        parsed_result.insert(SEX_FIELD.to_string(), sex);
-        parsed_result.insert(EXPIRY_DATE_FIELD.to_string(), expiry_date);
-        parsed_result.insert(OPTIONAL_DATA_1_FIELD.to_string(), optional_data);
-        parsed_result.insert(FINAL_CHECK_DIGIT_FIELD.to_string(), final_check_digit);
-
-        let issuing_state = ISSUING_COUNTRY_CODES
-            .get(&parsed_result[COUNTRY_CODE_FIELD].value as &str)
-            .unwrap_or(&"Unknown")
-            .to_string();
-
-        Ok(MRZResult {
-            is_visa,
-            is_valid,
-            fields: parsed_result,
-            issuing_state,
-        })
+        MRZResult::new(&self.options, document, kind, is_valid, repairs)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::mrz_field_name::{
+        BIRTHDATE_FIELD, DOCUMENT_NUMBER_FIELD, EXPIRY_DATE_FIELD, NAME_FIELD, NATIONALITY_FIELD, OPTIONAL_DATA_1_FIELD,
+    };
+    use crate::parser::correction::Substitution;
+    use crate::parser::mrz_date::{CenturyPivot, DateOptions, MrzDate};
+    use crate::parser::options::CorrectionMode;
+    use crate::parser::warning::ParseWarning;
 
     #[test]
     fn test_parse_td3() {
@@ -160,13 +200,243 @@ mod tests {
             "L898902C36UTO7408122F1204159ZE184226B<<<<<10".to_string(),
         ];
 
-        let mut td3 = TD3::new();
+        let td3 = TD3::new();
         let result = td3.parse(&mrz_string).unwrap();
-        assert_eq!(result.is_visa, false);
-        assert_eq!(result.is_valid, true);
+        assert!(!result.is_visa);
+        assert_eq!(result.kind, DocumentKind::Passport);
+        assert!(result.is_valid);
         println!("{:?}", result)
     }
 
+    #[test]
+    fn test_parse_td3_dates() {
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+
+        let options = ParserOptions {
+            dates: DateOptions {
+                pivot: CenturyPivot::default(),
+                reference_date: MrzDate::new(2010, 1, 1),
+            },
+            ..ParserOptions::default()
+        };
+        let result = TD3::with_options(options).parse(&mrz_string).unwrap();
+        let fields = result.fields();
+        assert_eq!(fields[BIRTHDATE_FIELD].get_date(), MrzDate::new(1974, 8, 12).as_ref());
+        assert_eq!(fields[EXPIRY_DATE_FIELD].get_date(), MrzDate::new(2012, 4, 15).as_ref());
+    }
+
+    #[test]
+    fn test_parse_td3_invalid_date() {
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO9913992F1204159ZE184226B<<<<<10".to_string(),
+        ];
+
+        let result = TD3::new().parse(&mrz_string);
+        assert_eq!(result.unwrap_err(), MrzError::InvalidDate("991399".to_string()));
+    }
+
+    #[test]
+    fn test_parse_td3_correction_modes() {
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSS0N<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C3GUTO74O8122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        let with_mode = |correction: CorrectionMode| {
+            TD3::with_options(ParserOptions {
+                correction,
+                ..ParserOptions::default()
+            })
+        };
+
+        let result = with_mode(CorrectionMode::Off).parse(&mrz_string);
+        assert_eq!(result.unwrap_err(), MrzError::InvalidDate("74O812".to_string()));
+
+        let result = with_mode(CorrectionMode::Conservative)
+            .parse(&mrz_string)
+            .unwrap();
+        let fields = result.fields();
+        assert!(!result.is_valid);
+        assert_eq!(fields[NAME_FIELD].value, "ERIKSS0N ANNA MARIA");
+        assert_eq!(
+            fields[BIRTHDATE_FIELD].corrections,
+            vec![Substitution {
+                position: 2,
+                from: 'O',
+                to: '0'
+            }]
+        );
+
+        let result = with_mode(CorrectionMode::Aggressive)
+            .parse(&mrz_string)
+            .unwrap();
+        assert_eq!(result.fields()[NAME_FIELD].value, "ERIKSSON ANNA MARIA");
+        assert_eq!(result.fields()[NAME_FIELD].corrections.len(), 1);
+
+        // The check digit `G` is read as `6` with a single edit. L/1 keeps every check digit, so it is not
+        // tried on the `L` of the document number.
+        let result = with_mode(CorrectionMode::CheckDigitGuided)
+            .parse(&mrz_string)
+            .unwrap();
+        let fields = result.fields();
+        assert!(result.is_valid);
+        assert_eq!(fields[DOCUMENT_NUMBER_FIELD].raw_value, "L898902C3");
+        assert_eq!(fields[DOCUMENT_NUMBER_FIELD].check_digit, "6");
+        assert_eq!(
+            fields[DOCUMENT_NUMBER_FIELD].corrections,
+            vec![Substitution {
+                position: 9,
+                from: 'G',
+                to: '6'
+            }]
+        );
+        assert_eq!(fields[BIRTHDATE_FIELD].raw_value, "740812");
+
+        // `S` is only read as `5` by the guided search, which finds a single reading
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F12041S9ZE184226B<<<<<10".to_string(),
+        ];
+        let result = with_mode(CorrectionMode::CheckDigitGuided)
+            .parse(&mrz_string)
+            .unwrap();
+        let fields = result.fields();
+        assert!(result.is_valid);
+        assert_eq!(fields[EXPIRY_DATE_FIELD].raw_value, "120415");
+        assert_eq!(
+            fields[EXPIRY_DATE_FIELD].corrections,
+            vec![Substitution {
+                position: 5,
+                from: 'S',
+                to: '5'
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_td3_confidence() {
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        assert!(result
+            .fields()
+            .values()
+            .all(|field| field.confidence == 1.0));
+        assert_eq!(result.quality(), 1.0);
+
+        // An unknown nationality and a corrected name lower their fields, a wrong check digit its field
+        // and the whole result
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSS0N<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C37XYZ7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        let fields = result.fields();
+        assert!((fields[NAME_FIELD].confidence - 0.8).abs() < 1e-9);
+        assert_eq!(fields[NATIONALITY_FIELD].confidence, 0.5);
+        assert!((fields[DOCUMENT_NUMBER_FIELD].confidence - 0.2).abs() < 1e-9);
+        assert_eq!(fields[BIRTHDATE_FIELD].confidence, 1.0);
+        assert!((result.quality() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_td3_country_codes() {
+        // Germany writes its code as `D<<`
+        let mrz_string: Vec<String> = vec![
+            "P<D<<MUSTERMANN<<ERIKA<<<<<<<<<<<<<<<<<<<<<<".to_string(),
+            "C01X00T478D<<6408125F2702283<<<<<<<<<<<<<<<4".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.issuing_state, "Germany");
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.issuing_country().map(|country| country.alpha3), Some("DEU"));
+        assert_eq!(result.nationality().map(|country| country.alpha2), Some("DE"));
+
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36XYZ7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(
+            result.warnings,
+            vec![ParseWarning::UnknownCode {
+                field: NATIONALITY_FIELD.to_string(),
+                code: "XYZ".to_string(),
+            }]
+        );
+
+        let options = ParserOptions {
+            strict: true,
+            ..ParserOptions::default()
+        };
+        assert_eq!(
+            TD3::with_options(options).parse(&mrz_string).unwrap_err(),
+            MrzError::UnknownCode {
+                field: NATIONALITY_FIELD,
+                code: "XYZ".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_td3_field_validity() {
+        // Each field reports its own check digit, so a wrong document number digit leaves the dates valid
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C37UTO7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        assert!(!result.get(DOCUMENT_NUMBER_FIELD).unwrap().is_valid);
+        assert!(result.get(BIRTHDATE_FIELD).unwrap().is_valid);
+        assert!(result.get(EXPIRY_DATE_FIELD).unwrap().is_valid);
+        assert!(!result.is_valid);
+
+        // An empty personal number may use a filler as check digit, read as 0
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122F1204159<<<<<<<<<<<<<<<8".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        let personal_number = result.get(OPTIONAL_DATA_1_FIELD).unwrap();
+        assert_eq!(personal_number.check_digit, "0");
+        assert!(personal_number.is_valid());
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn test_parse_td3_invalid_sex() {
+        let mrz_string: Vec<String> = vec![
+            "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<".to_string(),
+            "L898902C36UTO7408122P1204159ZE184226B<<<<<10".to_string(),
+        ];
+        assert_eq!(TD3::new().parse(&mrz_string).unwrap_err(), MrzError::InvalidSex("P".to_string()));
+    }
+
+    #[test]
+    fn test_parse_td3_names() {
+        // Names longer than the field are cut by the issuer, leaving no trailing filler
+        let mrz_string: Vec<String> = vec![
+            "P<UTONILAVADHANANANDA<<CHAYAPA<DEJTHAMRONG<K".to_string(),
+            "L898902C36UTO7408122F1204159ZE184226B<<<<<10".to_string(),
+        ];
+        let result = TD3::new().parse(&mrz_string).unwrap();
+        let name = result.get(NAME_FIELD).unwrap();
+        assert_eq!(name.value, "NILAVADHANANANDA CHAYAPA DEJTHAMRONG K");
+
+        let names = name.get_identifiers().unwrap();
+        assert_eq!(names.primary, "NILAVADHANANANDA");
+        assert_eq!(names.secondary, vec!["CHAYAPA", "DEJTHAMRONG", "K"]);
+        assert!(names.truncated);
+        assert_eq!(names.given_names_first(), "CHAYAPA DEJTHAMRONG K NILAVADHANANANDA");
+    }
+
     #[test]
     fn test_parse_td3_visa() {
         let mrz_string: Vec<String> = vec![
@@ -174,10 +444,12 @@ mod tests {
             "L8988901C4XXX4009078F96121096ZE184226B<<<<<<".to_string(),
         ];
 
-        let mut td3 = TD3::new();
+        // Visas are handed over to the MRV-A parser
+        let td3 = TD3::new();
         let result = td3.parse(&mrz_string).unwrap();
-        assert_eq!(result.is_visa, true);
-        assert_eq!(result.is_valid, true);
+        assert!(result.is_visa);
+        assert!(result.is_valid);
+        assert!(matches!(result.document, MrzDocument::MRVA(_)));
         println!("{:?}", result)
     }
 }
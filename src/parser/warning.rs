@@ -0,0 +1,86 @@
+use crate::constants::mrz_utils::lookup_code;
+use crate::error::mrz_error::MrzError;
+use crate::parser::mrz_field::MrzField;
+use crate::parser::options::ParserOptions;
+use std::fmt;
+
+// A problem found in an MRZ that does not prevent reading it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "warning", rename_all = "snake_case"))]
+pub enum ParseWarning {
+    // The issuing state or nationality is not in the ICAO 9303 country code table. The field is named
+    // after constants::mrz_field_name.
+    UnknownCode { field: String, code: String },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::UnknownCode { field, code } => write!(f, "unknown {} code {:?}", field, code),
+        }
+    }
+}
+
+// check_codes: Checks the issuing state and nationality codes against the ICAO 9303 table. An unknown code
+// fails strict parsing and is returned as a warning otherwise.
+pub(crate) fn check_codes(
+    options: &ParserOptions, codes: &[(&'static str, &MrzField)],
+) -> Result<Vec<ParseWarning>, MrzError> {
+    let mut warnings = Vec::new();
+
+    for &(field, code) in codes {
+        if lookup_code(&code.value).is_some() {
+            continue;
+        }
+        if options.strict {
+            return Err(MrzError::UnknownCode {
+                field,
+                code: code.value.clone(),
+            });
+        }
+        warnings.push(ParseWarning::UnknownCode {
+            field: field.to_string(),
+            code: code.value.clone(),
+        });
+    }
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::mrz_field_name::{COUNTRY_CODE_FIELD, NATIONALITY_FIELD};
+
+    fn code(value: &str) -> MrzField {
+        MrzField::new(value.to_string(), value.to_string(), String::new(), true)
+    }
+
+    #[test]
+    fn test_check_codes() {
+        let options = ParserOptions::default();
+        let (germany, unknown) = (code("D<<"), code("ZZZ"));
+
+        let warnings = check_codes(&options, &[(COUNTRY_CODE_FIELD, &germany), (NATIONALITY_FIELD, &unknown)]);
+        assert_eq!(
+            warnings,
+            Ok(vec![ParseWarning::UnknownCode {
+                field: NATIONALITY_FIELD.to_string(),
+                code: "ZZZ".to_string(),
+            }])
+        );
+
+        let options = ParserOptions {
+            strict: true,
+            ..ParserOptions::default()
+        };
+        assert_eq!(
+            check_codes(&options, &[(NATIONALITY_FIELD, &unknown)]),
+            Err(MrzError::UnknownCode {
+                field: NATIONALITY_FIELD,
+                code: "ZZZ".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::constants::mrz_utils::{
+    TYPE1_NUMBER_OF_CHARACTERS_PER_LINE, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE,
+};
+use crate::parser::options::ParserOptions;
+use crate::MRZParser;
+
+// Line layouts searched for, as (number of lines, characters per line)
+const LAYOUTS: [(usize, usize); 3] = [
+    (3, TYPE1_NUMBER_OF_CHARACTERS_PER_LINE),
+    (2, TYPE2_NUMBER_OF_CHARACTERS_PER_LINE),
+    (2, TYPE3_NUMBER_OF_CHARACTERS_PER_LINE),
+];
+
+// Number of characters a line may be missing or have in excess
+const LENGTH_TOLERANCE: usize = 2;
+
+// Minimum share of MRZ characters (A-Z, 0-9 and `<`) in a candidate line
+const MIN_MRZ_CHARACTER_RATIO: f64 = 0.9;
+
+// Minimum number of fillers in a candidate block. Every MRZ layout pads its names with them.
+const MIN_FILLERS: usize = 2;
+
+// A block of text that looks like an MRZ
+#[derive(Debug, Clone, PartialEq)]
+pub struct MrzCandidate {
+    // The MRZ lines, cleaned up. Lines off the length of the layout are left as read, for the parser to
+    // realign against the fields of their format.
+    pub lines: Vec<String>,
+    // Zero-based index of the first line of the block in the scanned text
+    pub first_line: usize,
+    // Likelihood of the block being an MRZ, between 0 and 1
+    pub score: f64,
+}
+
+impl MrzCandidate {
+    // parser: Returns a parser over the candidate lines, repairing the length of lines off the layout.
+    pub fn parser(&self) -> MRZParser {
+        repairing_parser(&self.lines)
+    }
+}
+
+// find_mrz_candidates: Finds the blocks of consecutive lines shaped like a TD1, TD2 or TD3 MRZ in OCR
+// output, best candidate first.
+//
+// Blank lines are skipped, spaces within lines are removed and `«` / `‹` are read as `<`. Lines may be
+// up to two characters off the length of the layout: they are parsed with
+// ParserOptions::repair_line_length, which inserts or removes the fillers realigning the fields of the
+// format wherever they are in the line. Candidates are scored on their share of MRZ characters, how close the
+// lines are to the expected length and how many of their check digits match.
+pub fn find_mrz_candidates(text: &str) -> Vec<MrzCandidate> {
+    let lines: Vec<(usize, String)> = text
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx, normalize_line(line)))
+        .filter(|(_, line)| !line.is_empty())
+        .collect();
+
+    let mut candidates = Vec::new();
+    for (line_count, line_length) in LAYOUTS {
+        for window in lines.windows(line_count) {
+            if let Some(candidate) = candidate(window, line_length) {
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.first_line.cmp(&b.first_line))
+    });
+    candidates
+}
+
+// Removes whitespace and maps the OCR renderings of the filler to `<`
+fn normalize_line(line: &str) -> String {
+    line.chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| match ch {
+            '«' | '‹' => '<',
+            ch => ch,
+        })
+        .collect()
+}
+
+fn is_mrz_character(ch: char) -> bool {
+    ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '<'
+}
+
+fn mrz_character_ratio(line: &str) -> f64 {
+    let total = line.chars().count();
+    let mrz = line.chars().filter(|&ch| is_mrz_character(ch)).count();
+    mrz as f64 / total as f64
+}
+
+// A parser over the lines, repairing the length of lines off the layout
+fn repairing_parser(lines: &[String]) -> MRZParser {
+    MRZParser::new_mrz_line_parser(lines.to_vec()).with_options(ParserOptions {
+        repair_line_length: true,
+        ..ParserOptions::default()
+    })
+}
+
+// Builds a candidate from consecutive lines if all of them could belong to an MRZ of the layout
+fn candidate(window: &[(usize, String)], line_length: usize) -> Option<MrzCandidate> {
+    let mut deviation = 0;
+    let mut ratio = 0.0;
+    for (_, line) in window {
+        let length = line.chars().count();
+        let line_ratio = mrz_character_ratio(line);
+        if length.abs_diff(line_length) > LENGTH_TOLERANCE || line_ratio < MIN_MRZ_CHARACTER_RATIO {
+            return None;
+        }
+        deviation += length.abs_diff(line_length);
+        ratio += line_ratio;
+    }
+
+    let fillers: usize = window
+        .iter()
+        .map(|(_, line)| line.matches('<').count())
+        .sum();
+    if fillers < MIN_FILLERS {
+        return None;
+    }
+
+    let lines: Vec<String> = window.iter().map(|(_, line)| line.clone()).collect();
+
+    let character_score = ratio / window.len() as f64;
+    let length_score = 1.0 - deviation as f64 / (window.len() * LENGTH_TOLERANCE) as f64;
+    let check_digit_score = check_digit_score(&lines);
+
+    Some(MrzCandidate {
+        score: 0.4 * character_score + 0.2 * length_score + 0.4 * check_digit_score,
+        first_line: window[0].0,
+        lines,
+    })
+}
+
+// Share of matching check digits, counting the composite check digit as one more. 0 if the lines do not parse.
+fn check_digit_score(lines: &[String]) -> f64 {
+    let Ok(result) = repairing_parser(lines).parse() else {
+        return 0.0;
+    };
+
+    let checked: Vec<bool> = result
+        .fields()
+        .values()
+        .filter(|field| !field.check_digit.is_empty() && field.check_digit != "<")
+        .map(|field| field.is_valid)
+        .chain([result.is_valid])
+        .collect();
+    checked.iter().filter(|&&valid| valid).count() as f64 / checked.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::mrz_field_name::FINAL_CHECK_DIGIT_FIELD;
+
+    #[test]
+    fn test_find_mrz_in_page() {
+        let text = "PASSPORT  PASSEPORT\n\
+                    Surname / Nom\n\
+                    ERIKSSON\n\
+                    Given names / Prénoms\n\
+                    ANNA MARIA\n\
+                    \n\
+                    P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\n\
+                    \n\
+                    L898902C36UTO7408122F1204159ZE184226B<<<<<10\n";
+
+        let candidates = find_mrz_candidates(text);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].first_line, 6);
+        assert_eq!(
+            candidates[0].lines,
+            vec!["P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<", "L898902C36UTO7408122F1204159ZE184226B<<<<<10"]
+        );
+        assert!((candidates[0].score - 1.0).abs() < 1e-9);
+
+        let result = candidates[0].parser().parse().unwrap();
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn test_find_mrz_with_ocr_artifacts() {
+        // Spaces within lines, `«` fillers, a line missing a filler and a line with a trailing extra one
+        let text = "I<UTOD231458907<<<<<<<<<<<<<<\n\
+                    7408122F1204159UTO «««««««««««6\n\
+                    ERIKSSON<<ANNA<MARIA<<<<<<<<<<<\n";
+
+        let candidates = find_mrz_candidates(text);
+        assert_eq!(
+            candidates[0].lines,
+            vec!["I<UTOD231458907<<<<<<<<<<<<<<", "7408122F1204159UTO<<<<<<<<<<<6", "ERIKSSON<<ANNA<MARIA<<<<<<<<<<<"]
+        );
+        assert!(candidates[0].score < 1.0);
+
+        let result = candidates[0].parser().parse().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(
+            result
+                .repairs
+                .iter()
+                .map(|repair| repair.line)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn test_find_mrz_with_filler_dropped_mid_line() {
+        // A filler is missing before the check digits at the end of the second line
+        let text = "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\n\
+                    L898902C36UTO7408122F1204159ZE184226B<<<<10\n";
+
+        let candidates = find_mrz_candidates(text);
+        assert_eq!(candidates[0].lines[1], "L898902C36UTO7408122F1204159ZE184226B<<<<10");
+        assert!((candidates[0].score - 0.95).abs() < 1e-9);
+
+        let result = candidates[0].parser().parse().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.repairs[0].line, 1);
+        assert_eq!(result.get(FINAL_CHECK_DIGIT_FIELD).unwrap().value, "0");
+    }
+
+    #[test]
+    fn test_rank_candidates() {
+        // An invalid block ranks below a valid one, and prose is not a candidate
+        let text = "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\n\
+                    L898902C37UTO7408123F1204150ZE184226B<<<<<10\n\
+                    The quick brown fox jumps over the lazy dog!\n\
+                    P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\n\
+                    L898902C36UTO7408122F1204159ZE184226B<<<<<10\n";
+
+        let candidates = find_mrz_candidates(text);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].first_line, 3);
+        assert_eq!(candidates[1].first_line, 0);
+        assert!(candidates[0].score > candidates[1].score);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::constants::mrz_utils::{ALPHABET_MAPPING, WEIGHTS};
+use crate::error::mrz_error::MrzError;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn is_value_valid(value: &str, check_digit: &str) -> bool {
     let mut total = 0;
@@ -6,7 +8,7 @@ pub fn is_value_valid(value: &str, check_digit: &str) -> bool {
     for (idx, ch) in value.to_uppercase().chars().enumerate() {
         let char_value = if ALPHABET_MAPPING.contains_key(&ch) {
             *ALPHABET_MAPPING.get(&ch).unwrap()
-        } else if ch.is_digit(10) {
+        } else if ch.is_ascii_digit() {
             ch.to_digit(10).unwrap() as i32
         } else if ch == '<' {
             0
@@ -19,18 +21,22 @@ pub fn is_value_valid(value: &str, check_digit: &str) -> bool {
     (total % 10).to_string() == check_digit
 }
 
-pub fn calculate_check_digits(value: &str) -> Result<String, &'static str> {
+pub fn calculate_check_digits(value: &str) -> Result<String, MrzError> {
     let mut total = 0;
 
     for (idx, ch) in value.to_uppercase().chars().enumerate() {
         let char_value = if ALPHABET_MAPPING.contains_key(&ch) {
             *ALPHABET_MAPPING.get(&ch).unwrap()
-        } else if ch.is_digit(10) {
+        } else if ch.is_ascii_digit() {
             ch.to_digit(10).unwrap() as i32
         } else if ch == '<' {
             0
         } else {
-            return Err("Invalid MRZ character");
+            return Err(MrzError::InvalidCharacter {
+                line: 0,
+                column: idx,
+                character: ch,
+            });
         };
 
         total += char_value * WEIGHTS[idx % WEIGHTS.len()];
@@ -40,7 +46,7 @@ pub fn calculate_check_digits(value: &str) -> Result<String, &'static str> {
 }
 
 pub fn replace_digits(input: &str) -> String {
-    let replacements = vec![('0', 'O'), ('1', 'I'), ('2', 'Z'), ('8', 'B')];
+    let replacements = [('0', 'O'), ('1', 'I'), ('2', 'Z'), ('8', 'B')];
 
     input
         .chars()
@@ -54,7 +60,7 @@ pub fn replace_digits(input: &str) -> String {
 }
 
 pub fn replace_letters(input: &str) -> String {
-    let replacements = vec![('O', '0'), ('Q', '0'), ('U', '0'), ('D', '0'), ('I', '1'), ('Z', '2'), ('B', '8')];
+    let replacements = [('O', '0'), ('Q', '0'), ('U', '0'), ('D', '0'), ('I', '1'), ('Z', '2'), ('B', '8')];
 
     input
         .chars()
@@ -67,15 +73,41 @@ pub fn replace_letters(input: &str) -> String {
         .collect()
 }
 
-pub fn trimming_filler(input: &str) -> String {
-    input.trim_matches('<').to_string()
+pub fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
 }
 
-pub fn check_same<T: PartialEq>(slice: &[T]) -> bool {
-    if slice.is_empty() {
-        return true;
+// Number of days in the month. An unknown year allows 29 days in February.
+pub fn days_in_month(year: Option<u16>, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_none_or(is_leap_year) => 29,
+        2 => 28,
+        _ => 0,
     }
-    slice.iter().all(|v| *v == slice[0])
+}
+
+// Current UTC date as (year, month, day), converted from the system clock
+pub fn current_date() -> (u16, u8, u8) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    civil_from_days((secs / 86_400) as i64)
+}
+
+// Converts a number of days since 1970-01-01 to a proleptic Gregorian (year, month, day)
+fn civil_from_days(days: i64) -> (u16, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u16, month as u8, day as u8)
 }
 
 #[cfg(test)]
@@ -84,4 +116,24 @@ mod tests {
 
     #[test]
     fn test_is_value_valid() {}
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+    }
+
+    #[test]
+    fn test_calculate_check_digits_invalid_character() {
+        assert_eq!(calculate_check_digits("L898902C3").unwrap(), "6");
+        assert_eq!(
+            calculate_check_digits("L89#902C3"),
+            Err(MrzError::InvalidCharacter {
+                line: 0,
+                column: 3,
+                character: '#'
+            })
+        );
+    }
 }